@@ -0,0 +1,27 @@
+/// Which Chinese character set a reader wants content in. Several `zh.*` sources in this
+/// workspace (`zh.boylove`, `zh.manhuagui`) already expose an "isTraditionalChinese" settings
+/// toggle; this is that same choice, pulled out so a new source can reuse the type instead of
+/// re-declaring its own two-variant enum.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Charset {
+	Simplified,
+	Traditional,
+}
+
+impl Charset {
+	pub fn from_is_traditional(is_traditional: bool) -> Self {
+		if is_traditional { Charset::Traditional } else { Charset::Simplified }
+	}
+}
+
+/// Picks between a source's simplified- and traditional-variant value (a base URL, a charset
+/// query param, …) per the reader's [`Charset`] setting. This is the actual pattern every `zh.*`
+/// source in this workspace uses today — none does real character-by-character s2t conversion, so
+/// there's no mapping table to extract; this just saves the next source from re-writing the same
+/// `if is_traditional { a } else { b }`.
+pub fn pick<T>(charset: Charset, simplified: T, traditional: T) -> T {
+	match charset {
+		Charset::Simplified => simplified,
+		Charset::Traditional => traditional,
+	}
+}
@@ -0,0 +1,106 @@
+//! The `{"errno": ..., "errmsg": ...}` envelope taxonomy, pulled out of `zh.zaimanhua::net` where
+//! it carried no zaimanhua-specific logic (no settings lookups, nothing else in that file touches
+//! it) — just a mapping from a handful of well-known errno ranges to a small, user-facing error
+//! taxonomy. Each source still owns its own errno-to-[`ErrorKind`] table, since the exact codes
+//! (and which ones get dedicated handling instead of falling through to the generic envelope
+//! check) differ per API; [`classify_errno`] only covers `zh.zaimanhua`'s fallback cases —
+//! codes worth distinguishing (e.g. "token expired, retry after refresh") stay in the owning
+//! source.
+
+use aidoku::{
+	Result, bail,
+	alloc::{String, format},
+	error,
+	prelude::*,
+};
+
+/// Small user-facing error taxonomy that an errno/transport failure gets classified into, instead
+/// of leaking raw "API error N: msg" strings to the reader. Deliberately coarse — covers the
+/// failure modes a user can actually act on (log back in, wait out a server blip).
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+	Network,
+	NeedsLogin,
+	Forbidden,
+	NotFound,
+	Server,
+}
+
+/// The language [`ErrorKind::label`] and the envelope messages below are rendered in. Plain and
+/// settings-free by design — this crate has no `defaults`/settings layer of its own, so each
+/// source decides its own language (typically from a `appearanceLanguage`-style setting) and
+/// passes it in here rather than this crate reaching out for it.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Lang {
+	Zh,
+	En,
+}
+
+impl ErrorKind {
+	pub fn label(self, lang: Lang) -> &'static str {
+		match (lang, self) {
+			(Lang::Zh, ErrorKind::Network) => "网络错误",
+			(Lang::Zh, ErrorKind::NeedsLogin) => "需要登录",
+			(Lang::Zh, ErrorKind::Forbidden) => "权限不足",
+			(Lang::Zh, ErrorKind::NotFound) => "内容不存在",
+			(Lang::Zh, ErrorKind::Server) => "服务器异常",
+			(Lang::En, ErrorKind::Network) => "Network error",
+			(Lang::En, ErrorKind::NeedsLogin) => "Needs login",
+			(Lang::En, ErrorKind::Forbidden) => "Forbidden",
+			(Lang::En, ErrorKind::NotFound) => "Not found",
+			(Lang::En, ErrorKind::Server) => "Server error",
+		}
+	}
+}
+
+/// Maps the errno ranges `zh.zaimanhua` sees today to the [`ErrorKind`] a user would recognize,
+/// factored out here so the next `zh.*` source can reuse it. Covers only the generic fallback
+/// case — codes with their own dedicated handling (token refresh, "not confirmed yet", paid-
+/// chapter access, …) are matched by the owning source before it ever reaches
+/// [`check_errno`]/[`check_api_response`].
+pub fn classify_errno(errno: i64) -> ErrorKind {
+	match errno {
+		401 | 99 => ErrorKind::NeedsLogin,
+		403 | 1001 => ErrorKind::Forbidden,
+		404 | 1004 => ErrorKind::NotFound,
+		_ => ErrorKind::Server,
+	}
+}
+
+fn missing_data_field(lang: Lang) -> String {
+	let label = ErrorKind::Server.label(lang);
+	match lang {
+		Lang::Zh => format!("{label}：响应缺少 data 字段"),
+		Lang::En => format!("{label}: response is missing its data field"),
+	}
+}
+
+fn envelope_error(lang: Lang, kind: ErrorKind, errmsg: &str, errno: i64) -> String {
+	match lang {
+		Lang::Zh => format!("{}：{errmsg}（错误码 {errno}）", kind.label(lang)),
+		Lang::En => format!("{}: {errmsg} (code {errno})", kind.label(lang)),
+	}
+}
+
+/// Checks the `{"errno": ..., "errmsg": ...}` envelope every v4-style API response in this
+/// workspace is wrapped in, returning the `data` field on success.
+pub fn check_errno(json: &serde_json::Value, lang: Lang) -> Result<serde_json::Value> {
+	let errno = json.get("errno").and_then(|v| v.as_i64()).unwrap_or(-1);
+	if errno != 0 {
+		let errmsg = json.get("errmsg").and_then(|v| v.as_str()).unwrap_or("Unknown error");
+		bail!("{}", envelope_error(lang, classify_errno(errno), errmsg, errno));
+	}
+	json.get("data").cloned().ok_or_else(|| error!("{}", missing_data_field(lang)))
+}
+
+/// Typed counterpart to [`check_errno`], for call sites that already deserialized straight into a
+/// `{errno, errmsg, data}` envelope instead of working with raw JSON. `errno`/`errmsg`/`data` are
+/// passed in rather than requiring a shared envelope type, since each source's envelope is its own
+/// `#[derive(Deserialize)]` struct (e.g. `zh.zaimanhua::models::ApiResponse<T>`) and there's
+/// nothing to gain from forcing them all through one generic type.
+pub fn check_api_response<T>(errno: i32, errmsg: &str, data: Option<T>, lang: Lang) -> Result<T> {
+	if errno != 0 {
+		bail!("{}", envelope_error(lang, classify_errno(errno as i64), errmsg, errno as i64));
+	}
+	data.ok_or_else(|| error!("{}", missing_data_field(lang)))
+}
@@ -0,0 +1,7 @@
+use alloc::{format, string::String};
+
+/// Lower-case hex md5 digest of `input`, the shape every `zh.*` source's login/signature endpoints
+/// actually want (a password hash, a request signature, …) instead of the raw `md5::Digest`.
+pub fn md5_hex(input: impl AsRef<[u8]>) -> String {
+	format!("{:x}", md5::compute(input))
+}
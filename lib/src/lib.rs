@@ -0,0 +1,26 @@
+#![no_std]
+
+//! Pieces shared by this workspace's `zh.*` sources that would otherwise get copy-pasted into
+//! every new one: the v4-style `{"errno": ..., "errmsg": ...}` envelope taxonomy, the
+//! `page * size < total` pagination check, an `Ongoing`/`Completed` status mapping (plus the
+//! `UpdateStrategy` it implies) and an md5 hex-digest helper. Pulled out of `zh.zaimanhua` (the
+//! first source built against this), so the next `zh.*` source depends on this crate instead of
+//! copying `zaimanhua/src/net.rs` by hand.
+//!
+//! Deliberately does NOT include a simplified/traditional character-conversion table: no `zh.*`
+//! source in this workspace actually does real s2t conversion today (checked `zh.boylove` and
+//! `zh.manhuagui`, the two with something s2t-adjacent) — both just pick between two hardcoded
+//! site variants off a settings flag. [`charset::pick`] extracts that pattern instead of inventing
+//! a character-mapping table nothing here has ever needed.
+
+extern crate alloc;
+
+pub mod charset;
+pub mod errno;
+mod hash;
+mod pagination;
+mod status;
+
+pub use hash::md5_hex;
+pub use pagination::has_next_page;
+pub use status::{ongoing_completed_status, update_strategy};
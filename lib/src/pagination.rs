@@ -0,0 +1,7 @@
+/// Whether a page-shaped list response (`{"page": ..., "size": ..., "total": ...}`, the shape
+/// every v4-style `zh.*` API this workspace talks to returns) has another page after this one.
+/// Pulled out of `zh.zaimanhua::helpers`, where this exact expression was duplicated across every
+/// list-shaped endpoint (search, filter/rank, history, reading record, …).
+pub fn has_next_page(page: i32, size: i32, total: i32) -> bool {
+	page * size < total
+}
@@ -0,0 +1,23 @@
+use aidoku::{MangaStatus, UpdateStrategy};
+
+/// Maps the `0`/`1` ongoing/completed convention several `zh.*` APIs use for a series' `status`
+/// field to a [`MangaStatus`], falling back to `Unknown` for anything else (a field that's absent,
+/// or a source whose API uses a third value this doesn't know about yet).
+pub fn ongoing_completed_status(status: Option<i32>) -> MangaStatus {
+	match status {
+		Some(1) => MangaStatus::Completed,
+		Some(0) => MangaStatus::Ongoing,
+		_ => MangaStatus::Unknown,
+	}
+}
+
+/// A completed series is never going to update again, so library update checks shouldn't keep
+/// re-polling it; everything else (ongoing, or a status this source couldn't determine) still
+/// gets checked normally. See `en.mangadistrict`'s own status-to-strategy mapping for the same
+/// pattern elsewhere in this workspace.
+pub fn update_strategy(status: MangaStatus) -> UpdateStrategy {
+	match status {
+		MangaStatus::Completed => UpdateStrategy::Never,
+		_ => UpdateStrategy::Always,
+	}
+}
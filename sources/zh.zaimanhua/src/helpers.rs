@@ -0,0 +1,298 @@
+use crate::{models, net, strings};
+use aidoku::{
+	ContentRating, Manga, MangaPageResult, Result, Viewer,
+	alloc::{String, Vec, format, string::ToString, vec},
+	imports::{net::Request, std::current_date},
+};
+
+/// Theme/tag keywords that mark a series as outright NSFW rather than merely suggestive.
+const NSFW_TAG_KEYWORDS: &[&str] = &["成人", "18", "H漫", "里番"];
+
+/// Theme/tag keywords that warrant a suggestive rating without going as far as NSFW.
+const SUGGESTIVE_TAG_KEYWORDS: &[&str] = &["伪娘", "后宫", "百合", "纯爱", "猎奇"];
+
+/// Infers a [`ContentRating`] from a series' theme tags, since this API never sends a rating
+/// field directly. Used by `models::MangaDetail::into_manga`.
+pub fn content_rating_from_tags(tags: &[String]) -> ContentRating {
+	if tags.iter().any(|tag| NSFW_TAG_KEYWORDS.iter().any(|keyword| tag.contains(keyword))) {
+		ContentRating::NSFW
+	} else if tags
+		.iter()
+		.any(|tag| SUGGESTIVE_TAG_KEYWORDS.iter().any(|keyword| tag.contains(keyword)))
+	{
+		ContentRating::Suggestive
+	} else {
+		ContentRating::Safe
+	}
+}
+
+/// Theme keyword marking a series as a vertical-scroll "条漫" (webtoon-style strip comic) rather
+/// than the traditional paged manhua this API defaults to — see `recommend/list`'s own "条漫专区"
+/// editorial category in [`fetch_recommend_categories`]. There's no dedicated reading-direction
+/// field on the detail endpoint to read this off instead (checked every field `models::MangaDetail`
+/// deserializes), so theme tags are the only signal available.
+const WEBTOON_TAG_KEYWORD: &str = "条漫";
+
+/// Infers a [`Viewer`] from a series' theme tags, since this API never sends a reading-direction
+/// field directly. Used by `models::MangaDetail::into_manga`.
+pub fn viewer_from_tags(tags: &[String]) -> Viewer {
+	if tags.iter().any(|tag| tag.contains(WEBTOON_TAG_KEYWORD)) {
+		Viewer::Webtoon
+	} else {
+		Viewer::RightToLeft
+	}
+}
+
+/// Formats a raw popularity count the way the official app's rank boards do: "X万" once it
+/// clears ten thousand, the bare number otherwise.
+pub fn format_popularity(count: i64) -> String {
+	if count >= 10_000 {
+		format!("热度 {:.1}万", count as f64 / 10_000.0)
+	} else {
+		format!("热度 {count}")
+	}
+}
+
+/// Picks which of a series' official localized title and original (usually Japanese) title
+/// becomes `Manga.title` per `settings::prefer_original_title`, folding the other into
+/// `description` as a labeled line so it isn't lost. Used by `models::MangaDetail::into_manga`.
+///
+/// This `aidoku` version's `Manga` has no dedicated alternate-titles field to put the other title
+/// in instead (checked every `Manga { .. }` literal in this workspace, including
+/// `multi.mangadex`'s own title-preference logic, which picks one title the same way rather than
+/// ever attaching a list to the struct) — folding it into `description` is the closest honest
+/// substitute available. The detail payload itself only ever carries the one alias
+/// (`original_title`); there's no separate list of aka names to also surface here.
+pub fn resolve_title(
+	official_title: String,
+	original_title: Option<String>,
+	description: Option<String>,
+) -> (String, Option<String>) {
+	let Some(original_title) = original_title.filter(|t| !t.is_empty() && *t != official_title) else {
+		return (official_title, description);
+	};
+
+	let (title, other_label, other_title) = if crate::settings::prefer_original_title() {
+		(original_title, "官方译名", official_title)
+	} else {
+		(official_title, "原名", original_title)
+	};
+
+	let note = format!("{other_label}：{other_title}");
+	let description = match description {
+		Some(description) if !description.is_empty() => Some(format!("{note}\n\n{description}")),
+		_ => Some(note),
+	};
+	(title, description)
+}
+
+/// Merges several [`MangaPageResult`]s into one, de-duplicating entries by [`Manga::key`].
+///
+/// Used when a single logical search (e.g. multiple author names) has to be split across
+/// several API calls.
+pub struct MangaMerger {
+	seen: Vec<String>,
+	entries: Vec<Manga>,
+	has_next_page: bool,
+}
+
+impl MangaMerger {
+	pub fn new() -> Self {
+		Self {
+			seen: Vec::new(),
+			entries: Vec::new(),
+			has_next_page: false,
+		}
+	}
+
+	pub fn push(&mut self, result: MangaPageResult) {
+		self.has_next_page |= result.has_next_page;
+		for manga in result.entries {
+			if !self.seen.contains(&manga.key) {
+				self.seen.push(manga.key.clone());
+				self.entries.push(manga);
+			}
+		}
+	}
+
+	pub fn finish(self) -> MangaPageResult {
+		MangaPageResult {
+			entries: self.entries,
+			has_next_page: self.has_next_page,
+		}
+	}
+}
+
+fn request_search_data(url: String) -> Result<models::SearchData> {
+	let mut response = Request::get(url)?.send()?;
+	let json: models::ApiResponse<models::SearchData> = response.get_json()?;
+	net::check_api_response(json)
+}
+
+fn list_page_result(data: models::ListData) -> MangaPageResult {
+	MangaPageResult {
+		entries: data.list.into_iter().map(Into::into).collect(),
+		has_next_page: zh_common::has_next_page(data.page, data.size, data.total),
+	}
+}
+
+/// Fetches any list-shaped endpoint (filter/rank/category/…) built on [`models::ListData`], the
+/// common-case counterpart to [`request_search_data`]'s dedicated search shape.
+pub fn fetch_list(url: String) -> Result<MangaPageResult> {
+	let mut response = Request::get(url)?.send()?;
+	let json: models::ApiResponse<models::ListData> = response.get_json()?;
+	Ok(list_page_result(net::check_api_response(json)?))
+}
+
+/// Authenticated counterpart to [`fetch_list`], for list endpoints behind login (subscriptions,
+/// the hidden-content index, …).
+pub fn fetch_authed_list(path: &str) -> Result<MangaPageResult> {
+	let json: models::ApiResponse<models::ListData> =
+		net::fetch_authed(path, crate::settings::get_retry_attempts())?;
+	Ok(list_page_result(net::check_api_response(json)?))
+}
+
+/// Keyword search built on the typed [`models`] stack, wired to `Source::get_search_manga_list`.
+pub fn search_by_keyword(keyword: &str, page: i32) -> Result<MangaPageResult> {
+	let size = crate::settings::get_page_size();
+	let data = request_search_data(net::urls::search(keyword, page, size))?;
+	Ok(MangaPageResult {
+		entries: data.list.into_iter().map(Into::into).collect(),
+		has_next_page: zh_common::has_next_page(data.page, data.size, data.total),
+	})
+}
+
+/// Caps how many per-name requests a single [`search_by_author`] call can fire. There's no sleep
+/// primitive in this wasm sandbox to space the requests out over time, so capping the count is
+/// the best available stand-in for a real rate limiter. Names past this are silently dropped
+/// rather than still fired at full speed.
+const MAX_AUTHOR_SEARCH_NAMES: usize = 5;
+
+/// Accepts several author names separated by `/` or `,` (doujin circles often credit more than
+/// one artist) by running a search per name and merging the results with [`MangaMerger`]. See
+/// [`MAX_AUTHOR_SEARCH_NAMES`] for why this doesn't just fire one request per name unconditionally.
+pub fn search_by_author(author: &str, page: i32) -> Result<MangaPageResult> {
+	let size = crate::settings::get_page_size();
+	let mut merger = MangaMerger::new();
+	for name in author
+		.split(['/', ','])
+		.map(str::trim)
+		.filter(|n| !n.is_empty())
+		.take(MAX_AUTHOR_SEARCH_NAMES)
+	{
+		let data = request_search_data(net::urls::search_by_author(name, page, size))?;
+		merger.push(MangaPageResult {
+			entries: data.list.into_iter().map(Into::into).collect(),
+			has_next_page: zh_common::has_next_page(data.page, data.size, data.total),
+		});
+	}
+	Ok(merger.finish())
+}
+
+/// Pulls the logged-in user's server-side reading history for the "浏览历史" listing.
+pub fn browse_history(page: i32) -> Result<MangaPageResult> {
+	let json: models::ApiResponse<models::HistoryData> = net::fetch_authed(
+		&format!("/app/v1/user/history?page={page}&size=20"),
+		crate::settings::get_retry_attempts(),
+	)?;
+	let data = net::check_api_response(json)?;
+	Ok(MangaPageResult {
+		entries: data.list.into_iter().map(Into::into).collect(),
+		has_next_page: zh_common::has_next_page(data.page, data.size, data.total),
+	})
+}
+
+/// Fetches every editorial category from `recommend/list` (the id-109 banner plus whatever else
+/// the app's home page curates, e.g. 条漫专区, 热门连载), rather than filtering server-side for a
+/// single category id.
+pub fn fetch_recommend_categories() -> Result<Vec<models::RecommendCategory>> {
+	let raw = net::fetch_deduped_json("recommendList", || {
+		Request::get(format!("{}/app/v1/comic/recommend/list", net::api_url()))?
+			.send()?
+			.get_json()
+	})?;
+	let json: models::ApiResponse<Vec<models::RecommendCategory>> = serde_json::from_value(raw)
+		.map_err(|_| aidoku::error!("{}", strings::response_unparseable()))?;
+	net::check_api_response(json)
+}
+
+/// Pulls series with unread progress from the account's reading-record API, for the
+/// "继续阅读" listing. Distinct from [`browse_history`], which is the raw browse history.
+pub fn continue_reading(page: i32) -> Result<MangaPageResult> {
+	let json: models::ApiResponse<models::RecordData> = net::fetch_authed(
+		&format!("/app/v1/user/record?page={page}&size=20"),
+		crate::settings::get_retry_attempts(),
+	)?;
+	let data = net::check_api_response(json)?;
+	Ok(MangaPageResult {
+		entries: data.list.into_iter().map(Into::into).collect(),
+		has_next_page: zh_common::has_next_page(data.page, data.size, data.total),
+	})
+}
+
+/// Some endpoints return Unix timestamps in milliseconds instead of seconds. Detects the
+/// magnitude (seconds won't pass 10 billion until the year 2286) and scales down accordingly, so
+/// a parser that always assumed seconds doesn't render dates in year 55000. Shared by every
+/// timestamp field this crate parses, in `models.rs` and `home.rs` alike.
+pub fn normalize_timestamp_seconds(timestamp: i64) -> i64 {
+	if timestamp > 10_000_000_000 {
+		timestamp / 1000
+	} else {
+		timestamp
+	}
+}
+
+/// Formats a unix timestamp as a short relative label ("3小时前", "昨天", "3天前", …) for display
+/// next to a manga's last-updated chapter. Shared by `models::ListItem`'s conversion to `Manga`.
+pub fn format_relative_time(timestamp: i64) -> String {
+	let timestamp = normalize_timestamp_seconds(timestamp);
+	let delta = (current_date() - timestamp).max(0);
+	match delta {
+		d if d < 3600 => format!("{}分钟前", (d / 60).max(1)),
+		d if d < 86400 => format!("{}小时前", d / 3600),
+		d if d < 2 * 86400 => "昨天".to_string(),
+		d if d < 30 * 86400 => format!("{}天前", d / 86400),
+		_ => format!("{}个月前", delta / (30 * 86400)),
+	}
+}
+
+/// Strips noise that shows up verbatim in some series' chapter titles: the manga's own title
+/// repeated at the front (with a separator), and bracket pairs left empty once that's gone.
+/// Used by `models::MangaDetail::into_chapters` behind the opt-in "chapterTitleCleanup" setting.
+pub fn clean_chapter_title(title: &str, manga_title: &str) -> String {
+	let mut cleaned = title.trim();
+	if !manga_title.is_empty()
+		&& let Some(rest) = cleaned.strip_prefix(manga_title)
+	{
+		cleaned = rest.trim_start_matches(['-', '：', ':', '_', ' ']).trim();
+	}
+
+	cleaned
+		.replace("()", "")
+		.replace("【】", "")
+		.replace("[]", "")
+		.replace("（）", "")
+		.trim()
+		.to_string()
+}
+
+/// Only worth labeling a chapter with its chapter group when a series actually has more than one
+/// group — for the common single-group case ("连载") the label is the same noise word repeated on
+/// every row. Used by `models::MangaDetail::into_chapters`.
+pub fn scanlators_for_group(name: Option<String>, group_count: usize) -> Option<Vec<String>> {
+	if group_count > 1 { name.map(|s| vec![s]) } else { None }
+}
+
+/// Hidden-content can currently only be found by guessing a keyword that happens to hit a
+/// restricted work and hoping the search endpoint surfaces it. Kept around as the fallback for
+/// callers that don't page through the dedicated hidden index (see `lib.rs`'s `hidden` listing).
+///
+/// Fires exactly one request per call, so there's no burst here for a rate limiter to gate —
+/// unlike [`search_by_author`], this isn't a loop over several requests.
+pub fn guess_hidden_by_keyword(keyword: &str) -> Result<Vec<Manga>> {
+	if !crate::settings::is_enhanced_mode() {
+		return Ok(vec![]);
+	}
+	let result = search_by_keyword(keyword, 1)?;
+	Ok(result.entries)
+}
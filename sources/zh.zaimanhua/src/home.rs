@@ -0,0 +1,654 @@
+use crate::{Zaimanhua, helpers, models, net, settings, strings};
+use aidoku::{
+	Home, HomeComponent, HomeComponentValue, HomeLayout, Link, Listing, Manga, Result,
+	alloc::{String, Vec, format, string::ToString},
+	imports::{
+		html::Html,
+		net::{Request, RequestError, Response},
+		std::current_date,
+	},
+};
+use chrono::{TimeZone, Utc};
+
+/// Editorial banner category id on the `recommend/list` endpoint.
+const BANNER_CATEGORY_ID: i32 = 109;
+
+// No genre quick-links chip grid here: `HomeComponentValue` only has manga-backed variants, no
+// bare grid of tappable text/category links. Browsing by 题材 already exists as the `theme-*`
+// listings registered in `res/source.json` and reachable from the Browse tab.
+
+enum SectionValue {
+	Scroller,
+	BigScroller,
+}
+
+/// One of the home page's togglable, independently-retried sections (see `res/settings.json`'s
+/// "首页显示" group). `key` doubles as the settings toggle key and the cache key.
+struct Section {
+	key: &'static str,
+	title: Option<&'static str>,
+	listing_id: &'static str,
+	url: String,
+	value: SectionValue,
+}
+
+fn enabled_sections(size: i32) -> Vec<Section> {
+	let mut sections = Vec::new();
+
+	let mut push = |key, title, listing_id, url, value| {
+		if settings::is_home_section_enabled(key) {
+			sections.push(Section { key, title, listing_id, url, value });
+		}
+	};
+
+	push(
+		"homeRecommend",
+		Some(strings::home_title("recommend")),
+		"recommend",
+		format!(
+			"{}/app/v1/comic/filter?sort=recommend&page=1&size={size}",
+			net::api_url()
+		),
+		SectionValue::Scroller,
+	);
+	// Three adjacent rank tabs instead of a single monthly-only list, so hot-right-now titles
+	// aren't a month stale.
+	for (by_time, listing_id) in
+		[("day", "rank-daily"), ("week", "rank-weekly"), ("month", "rank-monthly")]
+	{
+		push(
+			"homeRank",
+			Some(strings::home_title(listing_id)),
+			listing_id,
+			format!(
+				"{}/app/v1/comic/rank?by_time={by_time}&page=1&size={size}",
+				net::api_url()
+			),
+			SectionValue::Scroller,
+		);
+	}
+	push(
+		"homeLatest",
+		Some(strings::home_title("latest")),
+		"latest",
+		format!(
+			"{}/app/v1/comic/filter?sort=new&page=1&size={size}",
+			net::api_url()
+		),
+		SectionValue::Scroller,
+	);
+	for (key, audience) in [
+		("homeShounen", "shounen"),
+		("homeShoujo", "shoujo"),
+		("homeQingnian", "qingnian"),
+		("homeOther", "other"),
+	] {
+		push(
+			key,
+			Some(strings::home_title(audience)),
+			audience,
+			format!(
+				"{}/app/v1/comic/filter?audience={audience}&page=1&size={size}",
+				net::api_url()
+			),
+			SectionValue::Scroller,
+		);
+	}
+
+	for (key, zone) in [("homeCn", "cn"), ("homeKr", "kr")] {
+		push(
+			key,
+			Some(strings::home_title(zone)),
+			zone,
+			format!(
+				"{}/app/v1/comic/filter?zone={zone}&page=1&size={size}",
+				net::api_url()
+			),
+			SectionValue::Scroller,
+		);
+	}
+
+	sections
+}
+
+fn send_all(sections: &[Section]) -> Result<Vec<core::result::Result<Response, RequestError>>> {
+	let requests = sections
+		.iter()
+		.map(|section| Request::get(section.url.clone()))
+		.collect::<Result<Vec<_>>>()?;
+	Ok(Request::send_all(requests))
+}
+
+fn extract_data(
+	url: &str,
+	started_at: i64,
+	response: core::result::Result<Response, RequestError>,
+) -> Option<serde_json::Value> {
+	let mut response = response.ok()?;
+	let json: serde_json::Value = response.get_json().ok()?;
+	net::debug_log(url, json.get("errno").and_then(|v| v.as_i64()), current_date() - started_at);
+	net::check_errno(&json).ok()
+}
+
+fn build_component(section: &Section, data: serde_json::Value) -> Result<Option<HomeComponent>> {
+	let list_data: models::ListData = serde_json::from_value(data)
+		.map_err(|_| aidoku::error!("{}", strings::response_unparseable()))?;
+	if list_data.list.is_empty() {
+		return Ok(None);
+	}
+
+	let entries = list_data
+		.list
+		.into_iter()
+		.map(Into::<Manga>::into)
+		.map(Into::into)
+		.collect();
+	let value = match section.value {
+		SectionValue::BigScroller => HomeComponentValue::BigScroller {
+			entries,
+			auto_scroll_interval: Some(8.0),
+		},
+		_ => HomeComponentValue::Scroller {
+			entries,
+			listing: Some(Listing {
+				id: section.listing_id.into(),
+				name: section.title.unwrap_or_default().into(),
+				..Default::default()
+			}),
+		},
+	};
+
+	Ok(Some(HomeComponent {
+		title: section.title.map(Into::into),
+		subtitle: None,
+		value,
+	}))
+}
+
+/// Fetches every enabled section in parallel, retries whichever ones failed with a second
+/// `send_all` round, and falls back to the last cached response for any that are still down
+/// rather than rendering them empty. Each component is tagged with its section key so
+/// [`apply_home_order`] can later reorder them.
+fn fetch_sections(sections: Vec<Section>) -> Result<Vec<(&'static str, HomeComponent)>> {
+	let started_at = current_date();
+	let mut responses = send_all(&sections)?;
+
+	let failed_indices = responses
+		.iter()
+		.enumerate()
+		.filter(|(_, res)| !matches!(res, Ok(res) if res.status_code() < 400))
+		.map(|(i, _)| i)
+		.collect::<Vec<_>>();
+	if !failed_indices.is_empty() {
+		let retry_sections = failed_indices.iter().map(|&i| &sections[i]).collect::<Vec<_>>();
+		let retry_requests = retry_sections
+			.iter()
+			.map(|section| Request::get(section.url.clone()))
+			.collect::<Result<Vec<_>>>()?;
+		let retry_responses = Request::send_all(retry_requests);
+		for (&index, response) in failed_indices.iter().zip(retry_responses) {
+			responses[index] = response;
+		}
+	}
+
+	// All sections in one `send_all` round share the same measured duration, since they're fired
+	// concurrently rather than one after another — this is an approximation of each request's own
+	// timing, not an exact one, but still points at "the whole home page was slow" in a report.
+	let mut components = Vec::new();
+	for (section, response) in sections.iter().zip(responses) {
+		let data = match extract_data(&section.url, started_at, response) {
+			Some(data) => {
+				settings::cache_home_section(section.key, &data.to_string());
+				Some(data)
+			}
+			None => settings::get_cached_home_section(section.key)
+				.and_then(|cached| serde_json::from_str(&cached).ok()),
+		};
+		let Some(data) = data else { continue };
+		if let Some(component) = build_component(section, data)? {
+			components.push((section.key, component));
+		}
+	}
+
+	Ok(components)
+}
+
+/// Check-in status for the "我的订阅" card's subtitle. There's no standalone info-card component
+/// in the home API, so today's sign-in state/streak rides along on the first logged-in-only
+/// component instead; tapping "每日签到" in settings is still what actually signs in.
+fn checkin_subtitle() -> Option<String> {
+	let info = net::get_user_info().ok()?;
+	let signed_today = info.get("is_sign").and_then(|v| v.as_bool()).unwrap_or(false);
+	let streak = info.get("sign_days").and_then(|v| v.as_i64()).unwrap_or(0);
+	Some(if signed_today {
+		format!("今日已签到 · 连续{streak}天")
+	} else {
+		format!("今日未签到 · 连续{streak}天 · 可在设置中签到")
+	})
+}
+
+/// Formats a unix timestamp as a bare date, for `format_account_info`'s registration date and VIP
+/// expiry — only the day matters for either of those, not the time of day.
+fn format_date(timestamp: i64) -> Option<String> {
+	Utc.timestamp_opt(timestamp, 0).single().map(|dt| dt.format("%Y-%m-%d").to_string())
+}
+
+/// Formats the logged-in account's nickname, VIP status/expiry, points, check-in streak and
+/// registration date for the `accountInfoDisplay` settings text field — the closest this `aidoku`
+/// version's settings UI gets to an account info footer, since there's no dedicated settings trait
+/// to render one with. Avatar is included as a bare URL for the same reason: there's no
+/// image-display setting type to actually render it with.
+fn format_account_info(info: &models::UserInfo) -> String {
+	let mut lines = Vec::new();
+	if let Some(nickname) = info.nickname.as_deref().filter(|s| !s.is_empty()) {
+		lines.push(format!("昵称：{nickname}"));
+	}
+	lines.push(format!(
+		"VIP：{}{}",
+		if info.vip == 1 { "是" } else { "否" },
+		info.vip_expire_time
+			.and_then(format_date)
+			.map(|date| format!("（至 {date}）"))
+			.unwrap_or_default()
+	));
+	lines.push(format!("积分：{} · 连续签到：{}天", info.point, info.sign_days));
+	if let Some(date) = info.register_time.and_then(format_date) {
+		lines.push(format!("注册日期：{date}"));
+	}
+	if let Some(avatar) = info.avatar.as_deref().filter(|s| !s.is_empty()) {
+		lines.push(format!("头像：{avatar}"));
+	}
+	lines.join("\n")
+}
+
+/// Warns about account states that make some API calls (subscribe, check-in) fail silently
+/// otherwise — an unbound phone/email, or a server-side restricted status — so the failure shows
+/// up here instead of as a confusing error the next time one of those buttons is pressed.
+fn account_warning_text(info: &models::UserInfo) -> String {
+	let mut warnings = Vec::new();
+
+	let mobile_bound = info.mobile.as_deref().is_some_and(|s| !s.is_empty());
+	let email_bound = info.email.as_deref().is_some_and(|s| !s.is_empty());
+	if !mobile_bound && !email_bound {
+		warnings.push("⚠️ 账号未绑定手机号或邮箱，订阅、签到等功能可能因账号校验失败而无法使用");
+	}
+
+	// Non-zero `status` means the account is in some restricted state (e.g. under review,
+	// banned) — the exact codes aren't documented, so this only distinguishes "normal" (0) from
+	// "not normal" rather than naming the specific restriction.
+	if info.status != 0 {
+		warnings.push("⚠️ 账号状态异常，部分功能可能无法正常使用");
+	}
+
+	warnings.join("\n")
+}
+
+/// Refreshes `accountInfoDisplay` and `accountWarningDisplay` after whatever just happened might
+/// have changed them (login, check-in, …). Best-effort — silently does nothing when logged out or
+/// offline.
+pub fn refresh_account_info_display() {
+	let Ok(info) = net::get_typed_user_info() else { return };
+	settings::set_account_info_display(&format_account_info(&info));
+	settings::set_account_warning_display(&account_warning_text(&info));
+}
+
+/// Runs the day's task list: claims the reward for anything already completed but unclaimed, then
+/// formats every task's name/reward/state for the `dailyTasksDisplay` settings text field. Tasks
+/// that aren't done yet (e.g. "阅读15分钟") can't be completed from here — only claimed once the
+/// app's own usage tracking marks them done server-side.
+pub fn run_daily_tasks() -> Result<String> {
+	let tasks = net::fetch_daily_tasks()?;
+	let mut lines = Vec::new();
+	for task in &tasks {
+		let name = task.get("name").and_then(|v| v.as_str()).unwrap_or("未知任务");
+		let reward = task.get("reward").and_then(|v| v.as_i64()).unwrap_or(0);
+		let task_id = task.get("id").and_then(|v| v.as_str());
+		let status = task.get("status").and_then(|v| v.as_i64()).unwrap_or(0);
+
+		let state = match (status, task_id) {
+			(1, Some(task_id)) => match net::claim_task_reward(task_id) {
+				Ok(()) => format!("已领取 +{reward}积分"),
+				Err(_) => "领取失败".to_string(),
+			},
+			(2, _) => format!("已领取 +{reward}积分"),
+			_ => "未完成".to_string(),
+		};
+		lines.push(format!("{name}：{state}"));
+	}
+
+	Ok(if lines.is_empty() {
+		"暂无每日任务".to_string()
+	} else {
+		lines.join("\n")
+	})
+}
+
+/// Diffs the server's subscribe list against the `desiredShelfIds` text setting and resolves both
+/// directions: ids only in the desired list get subscribed, ids only on the server get
+/// unsubscribed. The desired list is always the source of truth, so removing an id from it drops
+/// the server subscription rather than leaving it stale.
+///
+/// This `aidoku` version gives a source no way to ask Aidoku what's actually on the shelf, so
+/// there's no real local side to diff against. `desiredShelfIds` is a manually maintained
+/// substitute rather than a fabricated call into an API that doesn't exist.
+pub fn run_subscription_sync() -> Result<String> {
+	let desired = settings::get_desired_shelf_ids();
+	let remote = net::fetch_all_subscribed_ids()?;
+
+	let mut added = 0;
+	let mut removed = 0;
+	for id in &desired {
+		if !remote.contains(id) && net::subscribe(id).is_ok() {
+			added += 1;
+		}
+	}
+	for id in &remote {
+		if !desired.contains(id) && net::unsubscribe(id).is_ok() {
+			removed += 1;
+		}
+	}
+
+	Ok(format!("同步完成：新增订阅{added}个，取消订阅{removed}个"))
+}
+
+/// Builds the "我的订阅" scroller from the logged-in user's subscription list.
+fn fetch_subscriptions(size: i32) -> Result<HomeComponent> {
+	let result = helpers::fetch_authed_list(&net::urls::sub_list(1, size))?;
+
+	Ok(HomeComponent {
+		title: Some(strings::home_title("subscribe").into()),
+		subtitle: checkin_subtitle(),
+		value: HomeComponentValue::Scroller {
+			entries: result.entries.into_iter().map(Into::into).collect(),
+			listing: Some(Listing {
+				id: "subscribe".into(),
+				name: strings::home_title("subscribe").into(),
+				..Default::default()
+			}),
+		},
+	})
+}
+
+/// Builds the "继续阅读" scroller from the account's reading records, similar to the official
+/// app's 继续阅读 strip. Falls back to the plain browse history if the record endpoint has
+/// nothing to say (e.g. a brand-new account with no unread progress yet).
+fn fetch_continue_reading() -> Result<HomeComponent> {
+	let result = match helpers::continue_reading(1) {
+		Ok(result) if !result.entries.is_empty() => result,
+		_ => helpers::browse_history(1)?,
+	};
+
+	Ok(HomeComponent {
+		title: Some(strings::home_title("continue").into()),
+		subtitle: None,
+		value: HomeComponentValue::Scroller {
+			entries: result.entries.into_iter().map(Into::into).collect(),
+			listing: Some(Listing {
+				id: "continue".into(),
+				name: strings::home_title("continue").into(),
+				..Default::default()
+			}),
+		},
+	})
+}
+
+/// Splits the editorial `recommend/list` categories into the banner (category id 109) and
+/// everything else, each of the latter becoming its own scroller.
+///
+/// Deliberately renders straight from the title/cover `recommend/list` already returns instead
+/// of issuing a detail request per banner item — `Source::get_manga_update` fetches the rest
+/// lazily once a reader actually opens one, so the home page stays a single request round-trip.
+fn fetch_recommend_components() -> Result<Vec<(&'static str, HomeComponent)>> {
+	let categories = helpers::fetch_recommend_categories()?;
+	let (banner, rest): (Vec<_>, Vec<_>) = categories
+		.into_iter()
+		.partition(|category| category.category_id == BANNER_CATEGORY_ID);
+	let mut components = Vec::new();
+
+	if settings::is_home_section_enabled("homeBanner")
+		&& let Some(banner) = banner.into_iter().next()
+		&& !banner.list.is_empty()
+	{
+		components.push((
+			"homeBanner",
+			HomeComponent {
+				title: None,
+				subtitle: None,
+				value: HomeComponentValue::BigScroller {
+					entries: banner.list.into_iter().map(Into::into).collect(),
+					auto_scroll_interval: Some(8.0),
+				},
+			},
+		));
+	}
+
+	if settings::is_home_section_enabled("homeRecommendCategories") {
+		for category in rest {
+			if category.list.is_empty() {
+				continue;
+			}
+			components.push((
+				"homeRecommendCategories",
+				HomeComponent {
+					title: Some(category.category_name.into()),
+					subtitle: None,
+					value: HomeComponentValue::Scroller {
+						entries: category.list.into_iter().map(Into::into).collect(),
+						listing: None,
+					},
+				},
+			));
+		}
+	}
+
+	Ok(components)
+}
+
+/// How many series the "今日推荐" section shows.
+const TODAY_PICK_COUNT: usize = 6;
+
+/// Builds a "今日推荐" section: a handful of well-rated series picked from the score rank,
+/// rotated by a day-based seed so the picks are stable for the whole day but change daily.
+fn fetch_today_pick(size: i32) -> Result<Option<HomeComponent>> {
+	let mut entries = helpers::fetch_list(format!(
+		"{}/app/v1/comic/rank?by_time=month&rank_type=score&page=1&size={size}",
+		net::api_url()
+	))?
+	.entries;
+	if entries.is_empty() {
+		return Ok(None);
+	}
+
+	let day = (current_date() / 86400) as usize;
+	entries.rotate_left(day % entries.len());
+	entries.truncate(TODAY_PICK_COUNT);
+
+	Ok(Some(HomeComponent {
+		title: Some(strings::home_title("today-pick").into()),
+		subtitle: None,
+		value: HomeComponentValue::Scroller {
+			entries: entries.into_iter().map(Into::into).collect(),
+			listing: None,
+		},
+	}))
+}
+
+/// Scrapes the news listing page for its banner images, titles and publish dates.
+///
+/// Walks real selectors (`.briefnews_con_li` article cards, `.dec_img img` for the banner
+/// image, `h3 a` for the headline, `.news_date` for the publish date) instead of manually
+/// splitting on a CDN path substring, so a path/CDN change doesn't silently kill the whole
+/// banner — only the one selector that actually moved.
+fn parse_manga_news_html(html: &str) -> Result<Vec<Link>> {
+	let doc = Html::parse(html)?;
+	let links = doc
+		.select(".briefnews_con_li")
+		.map(|items| {
+			items
+				.filter_map(|item| {
+					let image_url = item.select_first(".dec_img img")?.attr("src");
+					let title = item
+						.select_first("h3 a")
+						.and_then(|el| el.text())
+						.filter(|text| !text.is_empty());
+					let subtitle = item
+						.select_first(".news_date")
+						.and_then(|el| el.text())
+						.filter(|text| !text.is_empty());
+
+					Some(Link {
+						title: title.unwrap_or_default(),
+						subtitle,
+						image_url,
+						..Default::default()
+					})
+				})
+				.collect()
+		})
+		.unwrap_or_default();
+	Ok(links)
+}
+
+/// Builds the news `ImageScroller` banner from `{BASE_URL}/news`.
+fn fetch_news_banner() -> Result<Option<HomeComponent>> {
+	let html = Request::get(format!("{}/news", net::base_url()))?.string()?;
+	let links = parse_manga_news_html(&html)?;
+	if links.is_empty() {
+		return Ok(None);
+	}
+
+	Ok(Some(HomeComponent {
+		title: Some(strings::home_title("news").into()),
+		subtitle: None,
+		value: HomeComponentValue::ImageScroller {
+			links,
+			auto_scroll_interval: Some(5.0),
+			width: Some(340),
+			height: Some(170),
+		},
+	}))
+}
+
+/// Lets one component's transient failure drop just that component instead of — via a bare `?` —
+/// aborting the whole home layout, including every section that already succeeded. `fetch_sections`
+/// gets its own retry-plus-cache-fallback for this; the other fetchers below don't build off a
+/// single cacheable JSON payload the way sections do, so a plain catch-and-skip is the honest
+/// equivalent for them.
+fn fetch_component<T>(label: &str, fetch: impl FnOnce() -> Result<T>) -> Option<T> {
+	match fetch() {
+		Ok(value) => Some(value),
+		Err(_) => {
+			if settings::is_debug_logging_enabled() {
+				settings::append_debug_log(&format!("{label} · skipped (fetch failed)"));
+			}
+			None
+		}
+	}
+}
+
+/// Reorders keyed home components according to the user's `homeOrder` setting (a
+/// comma-separated list of section keys). Keys absent from the setting keep their original
+/// relative order and are placed after every key that was explicitly listed.
+fn apply_home_order(components: Vec<(&'static str, HomeComponent)>) -> Vec<HomeComponent> {
+	let order = settings::get_home_order();
+	if order.is_empty() {
+		return components.into_iter().map(|(_, component)| component).collect();
+	}
+
+	let mut ranked = components
+		.into_iter()
+		.enumerate()
+		.map(|(i, (key, component))| {
+			let rank = order.iter().position(|k| k == key).unwrap_or(order.len() + i);
+			(rank, component)
+		})
+		.collect::<Vec<_>>();
+	ranked.sort_by_key(|(rank, _)| *rank);
+	ranked.into_iter().map(|(_, component)| component).collect()
+}
+
+impl Home for Zaimanhua {
+	fn get_home(&self) -> Result<HomeLayout> {
+		let size = settings::get_page_size();
+
+		let mut components = Vec::new();
+
+		// Always shown first (ahead of the reorderable sections) so logged-in users see their
+		// own shelf before editorial content, regardless of their `homeOrder` setting. Each is
+		// skipped on its own failure (see `fetch_component`) instead of aborting the rest of the
+		// home layout over one flaky endpoint.
+		if settings::get_token().is_some() {
+			if let Some(component) = fetch_component("home/subscriptions", || fetch_subscriptions(size)) {
+				components.push(component);
+			}
+			if let Some(component) = fetch_component("home/continueReading", fetch_continue_reading) {
+				components.push(component);
+			}
+		}
+
+		let mut keyed = Vec::new();
+		if settings::is_home_section_enabled("homeBanner")
+			|| settings::is_home_section_enabled("homeRecommendCategories")
+		{
+			keyed.extend(fetch_component("home/recommend", fetch_recommend_components).unwrap_or_default());
+		}
+		if settings::is_home_section_enabled("homeTodayPick")
+			&& let Some(component) = fetch_component("home/todayPick", || fetch_today_pick(size)).flatten()
+		{
+			keyed.push(("homeTodayPick", component));
+		}
+		if settings::is_home_section_enabled("homeNews")
+			&& let Some(component) = fetch_component("home/news", fetch_news_banner).flatten()
+		{
+			keyed.push(("homeNews", component));
+		}
+		let sections = enabled_sections(size);
+		let fetched =
+			fetch_component("home/sections", || fetch_sections(sections)).unwrap_or_default();
+		keyed.extend(fetched);
+
+		components.extend(apply_home_order(keyed));
+
+		Ok(HomeLayout { components })
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::parse_manga_news_html;
+	use aidoku_test::aidoku_test;
+
+	const NEWS_PAGE_FIXTURE: &str = r#"
+		<html><body>
+			<ul class="briefnews_list">
+				<li class="briefnews_con_li">
+					<div class="dec_img"><img src="https://images.zaimanhua.com/news/article/1.jpg"></div>
+					<h3><a href="/news/1">新番上线公告</a></h3>
+					<span class="news_date">2026-08-01</span>
+				</li>
+				<li class="briefnews_con_li">
+					<div class="dec_img"><img src="https://images.zaimanhua.com/news/article/2.jpg"></div>
+					<h3><a href="/news/2"></a></h3>
+				</li>
+			</ul>
+		</body></html>
+	"#;
+
+	#[aidoku_test]
+	fn parse_manga_news_html_test() {
+		let links = parse_manga_news_html(NEWS_PAGE_FIXTURE).expect("parse_manga_news_html failed");
+		assert_eq!(links.len(), 2);
+
+		assert_eq!(links[0].title, "新番上线公告");
+		assert_eq!(links[0].subtitle.as_deref(), Some("2026-08-01"));
+		assert_eq!(
+			links[0].image_url.as_deref(),
+			Some("https://images.zaimanhua.com/news/article/1.jpg")
+		);
+
+		// A missing headline/date degrades to an empty title and no subtitle instead of failing
+		// the whole parse.
+		assert_eq!(links[1].title, "");
+		assert_eq!(links[1].subtitle, None);
+	}
+}
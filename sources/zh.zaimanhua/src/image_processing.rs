@@ -0,0 +1,43 @@
+use aidoku::{
+	ImageResponse, PageContext, Result,
+	imports::canvas::{Canvas, ImageRef, Rect},
+};
+
+/// The watermark strip some zaimanhua uploads carry is a fixed band glued to the bottom of the
+/// page rather than a fixed pixel height, so it has to be cropped as a ratio of the page's own
+/// height.
+const WATERMARK_HEIGHT_RATIO: f32 = 0.04;
+
+/// Crops the bottom watermark strip off a page, using the page's own dimensions from
+/// `PageContext` (set alongside the image URL in `lib.rs`'s `get_page_list`). Falls back to the
+/// untouched image when the dimensions aren't known, rather than guessing at a crop.
+///
+/// Those context dimensions are the API's *declared* `page_width`/`page_height`, which only match
+/// the bytes actually downloaded as long as nothing has resized them in transit. Low-data mode
+/// (`net::apply_low_data_scaling`) asks the CDN to downscale the image to a fixed 720px width, so
+/// with both settings on the declared dimensions are stale and cropping against them would cut the
+/// wrong region — skip the crop in that combination rather than risk a garbage or out-of-bounds
+/// `Rect`.
+pub fn trim_watermark(response: ImageResponse, context: Option<&PageContext>) -> Result<ImageRef> {
+	if crate::settings::is_low_data_mode() {
+		return Ok(response.image);
+	}
+
+	let Some((width, height)) = context.and_then(|context| {
+		let width: f32 = context.get("imgWidth")?.parse().ok()?;
+		let height: f32 = context.get("imgHeight")?.parse().ok()?;
+		Some((width, height))
+	}) else {
+		return Ok(response.image);
+	};
+
+	let trimmed_height = height * (1.0 - WATERMARK_HEIGHT_RATIO);
+	if trimmed_height <= 0.0 {
+		return Ok(response.image);
+	}
+
+	let mut canvas = Canvas::new(width, trimmed_height);
+	let rect = Rect::new(0.0, 0.0, width, trimmed_height);
+	canvas.copy_image(&response.image, rect, rect);
+	Ok(canvas.get_image())
+}
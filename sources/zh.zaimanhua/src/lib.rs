@@ -0,0 +1,521 @@
+#![no_std]
+
+mod helpers;
+mod home;
+mod image_processing;
+mod models;
+mod net;
+mod profiles;
+mod settings;
+mod strings;
+
+use aidoku::{
+	BasicLoginHandler, Chapter, DeepLinkHandler, DeepLinkResult, DynamicListings, FilterValue,
+	HashMap, ImageRequestProvider, ImageResponse, Listing, ListingProvider, Manga, MangaPageResult,
+	NotificationHandler, Page, PageContent, PageContext, PageImageProcessor, Result, Source,
+	WebLoginHandler,
+	alloc::{String, Vec, format, string::ToString},
+	bail,
+	imports::{canvas::ImageRef, net::Request, std::send_partial_result},
+	prelude::*,
+	register_source,
+};
+
+const CATEGORY_LISTING_PREFIX: &str = "category-";
+const THEME_LISTING_PREFIX: &str = "theme-";
+
+/// How many chapters to stream per `send_partial_result` call in `get_manga_update`, so a
+/// thousand-chapter series doesn't make the reader wait for the whole list at once.
+const CHAPTER_STREAM_BATCH_SIZE: usize = 200;
+
+struct Zaimanhua;
+
+impl Source for Zaimanhua {
+	fn new() -> Self {
+		validate_stored_token();
+		maybe_auto_checkin();
+		Self
+	}
+
+	fn get_search_manga_list(
+		&self,
+		query: Option<String>,
+		page: i32,
+		filters: Vec<FilterValue>,
+	) -> Result<MangaPageResult> {
+		if let Some(keyword) = query {
+			return helpers::search_by_keyword(&keyword, page);
+		}
+
+		for filter in filters {
+			if let FilterValue::Text { id, value } = filter
+				&& id == "author"
+			{
+				return helpers::search_by_author(&value, page);
+			}
+		}
+
+		let size = settings::get_page_size();
+		helpers::fetch_list(net::urls::filter(None, page, size))
+	}
+
+	fn get_manga_update(
+		&self,
+		mut manga: Manga,
+		needs_details: bool,
+		needs_chapters: bool,
+	) -> Result<Manga> {
+		let json: models::ApiResponse<models::MangaDetail> =
+			net::fetch_authed(&net::urls::detail(&manga.key), settings::get_retry_attempts())?;
+		let detail = net::check_api_response(json)?;
+
+		let mut remaining = needs_chapters.then(|| detail.into_chapters());
+
+		if needs_details {
+			manga = Manga {
+				chapters: manga.chapters,
+				..detail.into_manga()
+			};
+			if needs_chapters {
+				// Let the reader open the manga page immediately instead of waiting on a
+				// thousand-chapter series to finish parsing before anything shows up.
+				send_partial_result(&manga);
+			}
+		}
+
+		if let Some(mut remaining) = remaining {
+			// Series with 1000+ chapters make for a very long single update; stream it to the UI
+			// in batches (newest groups are already sorted first) rather than making the reader
+			// wait for the whole list to parse and serialize at once.
+			let mut sent = Vec::new();
+			while !remaining.is_empty() {
+				let take = remaining.len().min(CHAPTER_STREAM_BATCH_SIZE);
+				sent.extend(remaining.drain(..take));
+				manga.chapters = Some(core::mem::take(&mut sent));
+				send_partial_result(&manga);
+				sent = manga.chapters.take().unwrap_or_default();
+			}
+			manga.chapters = Some(sent);
+		}
+
+		Ok(manga)
+	}
+
+	fn get_page_list(&self, manga: Manga, chapter: Chapter) -> Result<Vec<Page>> {
+		let path = net::urls::chapter(&manga.key, &chapter.key);
+		// Chapter fetches occasionally fail transiently under load; worth a few immediate retries
+		// before giving up, since the server's own errno/errmsg (handled below) already covers the
+		// non-transient failures (locked, missing, needs login). Always at least as many attempts as
+		// `apiRetryAttempts`, with a floor of 3 since a chapter fetch blocks the whole page list.
+		let json = net::fetch_authed_json(&path, settings::get_retry_attempts().max(3))?;
+		let data = net::check_chapter_access(&json, chapter.url.as_deref())?;
+
+		// `page_url_hd` isn't always present (some older chapters were only ever uploaded at one
+		// resolution), so fall back to the standard array rather than erroring over a quality pick.
+		let quality_key = if settings::prefer_hd_images() { "page_url_hd" } else { "page_url" };
+		let sd_urls = data.get("page_url").and_then(|v| v.as_array());
+		let hd_urls = data.get(quality_key).and_then(|v| v.as_array());
+		let urls = hd_urls
+			.or(sd_urls)
+			.ok_or_else(|| aidoku::error!("Missing chapter.page_url"))?;
+		let using_hd = quality_key == "page_url_hd" && hd_urls.is_some();
+		// Only present for series uploaded with per-page dimensions; used by `image_processing`
+		// to crop the watermark strip as a ratio of the page's own height.
+		let widths = data.get("page_width").and_then(|v| v.as_array());
+		let heights = data.get("page_height").and_then(|v| v.as_array());
+
+		_ = net::sync_read_progress(&manga.key, &chapter.key);
+
+		Ok(urls
+			.iter()
+			.filter_map(|v| v.as_str())
+			.enumerate()
+			.map(|(idx, url)| {
+				let url = net::apply_low_data_scaling(net::rewrite_image_host(url));
+				// Attach the SD equivalent so `get_image_request` can fall back to it if the HD
+				// page turns out to 404, instead of losing the whole chapter over one bad page.
+				let sd_url = sd_urls
+					.and_then(|sd| sd.get(idx))
+					.and_then(|v| v.as_str())
+					.map(|url| net::apply_low_data_scaling(net::rewrite_image_host(url)));
+				// comicId/chapterId/pageIndex let `get_image_request` re-fetch a fresh signed URL
+				// if this one's signature has expired by the time the reader gets to it.
+				let mut context = PageContext::new();
+				context.insert(String::from("comicId"), manga.key.clone());
+				context.insert(String::from("chapterId"), chapter.key.clone());
+				context.insert(String::from("pageIndex"), idx.to_string());
+				if let Some(sd_url) = &sd_url
+					&& using_hd
+					&& *sd_url != url
+				{
+					context.insert(String::from("sdUrl"), sd_url.clone());
+				}
+				if let (Some(width), Some(height)) = (
+					widths.and_then(|w| w.get(idx)).and_then(|v| v.as_f64()),
+					heights.and_then(|h| h.get(idx)).and_then(|v| v.as_f64()),
+				) {
+					context.insert(String::from("imgWidth"), width.to_string());
+					context.insert(String::from("imgHeight"), height.to_string());
+				}
+
+				Page {
+					content: PageContent::url_context(url, context),
+					..Default::default()
+				}
+			})
+			.collect())
+	}
+}
+
+impl ImageRequestProvider for Zaimanhua {
+	fn get_image_request(&self, url: String, context: Option<PageContext>) -> Result<Request> {
+		// There's no hook to retry a page after the fact, so probe it up front — the extra
+		// request only costs anything on the rare page that's actually broken.
+		let status = Request::get(&url)?.send().ok().map(|response| response.status_code());
+
+		let request = match status {
+			Some(code) if code < 400 => Request::get(url),
+
+			// Signed URLs can go stale if a chapter sits open for a while; re-fetch a fresh one
+			// instead of surfacing the 403 to the reader.
+			Some(403) => {
+				let fresh = context.as_ref().and_then(|context| {
+					let comic_id = context.get("comicId")?;
+					let chapter_id = context.get("chapterId")?;
+					let index = context.get("pageIndex")?.parse::<usize>().ok()?;
+					net::refresh_page_url(comic_id, chapter_id, index, settings::prefer_hd_images()).ok()
+				});
+				Request::get(fresh.unwrap_or(url))
+			}
+
+			// Any other failure (most commonly a 404 on the HD-quality image) falls back to SD
+			// when we have it, instead of losing the whole chapter over one bad page.
+			_ => match context.as_ref().and_then(|c| c.get("sdUrl")) {
+				Some(sd_url) => Request::get(sd_url.clone()),
+				None => Request::get(url),
+			},
+		};
+
+		// Image hosts weren't getting any User-Agent/extra headers before — applying the same
+		// overrides as the API path (see `net::user_agent`/`net::apply_custom_headers`) so a
+		// network that fingerprints on the image CDN can be worked around too.
+		net::apply_custom_headers(request.map(|request| request.header("User-Agent", &net::user_agent())))
+	}
+}
+
+impl PageImageProcessor for Zaimanhua {
+	fn process_page_image(&self, response: ImageResponse, context: Option<PageContext>) -> Result<ImageRef> {
+		if !settings::is_watermark_trim_enabled() {
+			return Ok(response.image);
+		}
+		image_processing::trim_watermark(response, context.as_ref())
+	}
+}
+
+/// Checks a cached token against `userInfo/get` on startup, so an overnight expiry shows up here
+/// instead of as an auth error on the user's first search of the day. `net::get_user_info` already
+/// re-logs-in on errno 99 through `fetch_authed_json`, so this just needs to trigger that check;
+/// failures (no stored credentials, genuinely logged out, offline) are swallowed since startup
+/// shouldn't ever block on this.
+fn validate_stored_token() {
+	if settings::get_token().is_some() {
+		_ = net::get_user_info();
+	}
+}
+
+/// Signs in for the day on startup if "自动签到" is on and it hasn't already happened today (see
+/// `settings::has_checked_in_today`). Best-effort, same as `validate_stored_token` above — offline
+/// or already-signed-in just means nothing happens.
+fn maybe_auto_checkin() {
+	if settings::get_token().is_none() || !settings::is_auto_checkin_enabled() {
+		return;
+	}
+	if settings::has_checked_in_today() {
+		return;
+	}
+	if net::check_in().is_ok() {
+		settings::mark_checked_in_today();
+	}
+	home::refresh_account_info_display();
+}
+
+impl ListingProvider for Zaimanhua {
+	fn get_manga_list(&self, listing: Listing, page: i32) -> Result<MangaPageResult> {
+		let size = settings::get_page_size();
+		match listing.id.as_str() {
+			"rank-daily" | "rank-weekly" | "rank-monthly" | "rank-total" => {
+				let by_time = match listing.id.as_str() {
+					"rank-daily" => "day",
+					"rank-weekly" => "week",
+					"rank-monthly" => "month",
+					_ => "total",
+				};
+				helpers::fetch_list(net::urls::rank(by_time, "popular", page, size))
+			}
+
+			// Besides popularity, the rank API also serves 吐槽榜 (most-discussed) and
+			// 评分榜 (highest-rated) boards, always over the current month.
+			"rank-roast" | "rank-score" => {
+				let rank_type = if listing.id == "rank-roast" { "roast" } else { "score" };
+				helpers::fetch_list(net::urls::rank("month", rank_type, page, size))
+			}
+
+			// Editorial picks surfaced on the home page's "精品推荐" row.
+			"recommend" => helpers::fetch_list(net::urls::filter(Some("sort=recommend"), page, size)),
+
+			// Finished series sorted by popularity, for readers who want to binge something
+			// complete instead of waiting on weekly updates.
+			"finished-popular" => {
+				helpers::fetch_list(net::urls::filter(Some("status=1&sort=popular"), page, size))
+			}
+
+			// Shows "更新至第X话" alongside each entry since this endpoint carries chapter info.
+			"latest" => helpers::fetch_list(net::urls::filter(Some("sort=new"), page, size)),
+
+			// Sorted by 上架时间 (shelf time) rather than `latest`'s last-update time, so
+			// recently added series surface even if they haven't updated since.
+			"new" => helpers::fetch_list(net::urls::filter(Some("sort=shelf"), page, size)),
+
+			"shounen" | "shoujo" | "qingnian" | "other" => helpers::fetch_list(net::urls::filter(
+				Some(&format!("audience={}", listing.id)),
+				page,
+				size,
+			)),
+
+			// Mirrors the audience listings above, but sliced by zone instead.
+			"cn" | "jp" | "kr" | "west" => helpers::fetch_list(net::urls::filter(
+				Some(&format!("zone={}", listing.id)),
+				page,
+				size,
+			)),
+
+			"subscribe" => {
+				// Only the token is needed here; Enhanced Mode is reserved for hidden-content
+				// behavior and shouldn't gate the user's own subscription list.
+				if settings::get_token().is_none() {
+					bail!("{}", strings::please_log_in_for_subscriptions());
+				}
+				helpers::fetch_authed_list(&net::urls::sub_list(page, size))
+			}
+
+			"history" => {
+				if settings::get_token().is_none() {
+					bail!("{}", strings::please_log_in());
+				}
+				helpers::browse_history(page)
+			}
+
+			"continue" => {
+				if settings::get_token().is_none() {
+					bail!("{}", strings::please_log_in());
+				}
+				helpers::continue_reading(page)
+			}
+
+			"hidden" => {
+				if !settings::is_enhanced_mode() {
+					bail!("{}", strings::enhanced_mode_required());
+				}
+				helpers::fetch_authed_list(&format!("/app/v1/search/hide/index?page={page}&size={size}"))
+			}
+
+			id => {
+				if let Some(category) = id.strip_prefix(CATEGORY_LISTING_PREFIX) {
+					helpers::fetch_list(net::urls::filter(Some(&format!("category={category}")), page, size))
+				} else if let Some(theme) = id.strip_prefix(THEME_LISTING_PREFIX) {
+					helpers::fetch_list(format!(
+						"{}/app/v1/comic/filter/list?theme={theme}&page={page}&size={size}",
+						net::api_url()
+					))
+				} else {
+					bail!("{}", strings::invalid_listing(id))
+				}
+			}
+		}
+	}
+}
+
+impl DynamicListings for Zaimanhua {
+	fn get_dynamic_listings(&self) -> Result<Vec<Listing>> {
+		let url = format!("{}/app/v1/comic/category/list", net::api_url());
+		let started_at = aidoku::imports::std::current_date();
+		let mut response = Request::get(&url)?.send()?;
+		let json: models::ApiResponse<models::CategoryData> = response.get_json()?;
+		net::debug_log(&url, Some(i64::from(json.errno)), aidoku::imports::std::current_date() - started_at);
+		let data = net::check_api_response(json)?;
+		let hide_nsfw = settings::hide_nsfw_categories();
+
+		Ok(data
+			.list
+			.into_iter()
+			.filter(|category| {
+				!hide_nsfw
+					|| matches!(
+						helpers::content_rating_from_tags(&[category.name.clone()]),
+						aidoku::ContentRating::Safe
+					)
+			})
+			.map(|category| Listing {
+				id: format!("{CATEGORY_LISTING_PREFIX}{}", category.id),
+				name: category.name,
+				..Default::default()
+			})
+			.collect())
+	}
+}
+
+impl DeepLinkHandler for Zaimanhua {
+	fn handle_deep_link(&self, url: String) -> Result<Option<DeepLinkResult>> {
+		let mut splits = url.split('/').skip(3);
+		let deep_link_result = match splits.next() {
+			Some("view") => splits.next().map(|key| DeepLinkResult::Manga { key: key.into() }),
+			_ => None,
+		};
+		Ok(deep_link_result)
+	}
+}
+
+impl BasicLoginHandler for Zaimanhua {
+	fn handle_basic_login(&self, key: String, username: String, password: String) -> Result<bool> {
+		if key != "login" {
+			bail!("{}", strings::invalid_login_key(&key));
+		}
+
+		if username.is_empty() {
+			_ = net::logout();
+			settings::clear_account();
+			return Ok(false);
+		}
+
+		settings::set_username(&username)?;
+		settings::set_password(&password)?;
+		let logged_in = net::login().is_ok();
+		if logged_in {
+			if settings::auto_enable_enhanced_mode_after_login() {
+				settings::set_enhanced_mode(true);
+			}
+			home::refresh_account_info_display();
+		}
+		Ok(logged_in)
+	}
+}
+
+/// Fallback for accounts the password endpoint can't handle (captcha-gated, or signed up via a
+/// third-party OAuth provider the app offers but the v4 API's `/user/login` doesn't accept
+/// directly): loads the official login page and pulls the session token out of the cookies it
+/// sets on success, rather than speaking the password API at all.
+impl WebLoginHandler for Zaimanhua {
+	fn handle_web_login(&self, key: String, cookies: HashMap<String, String>) -> Result<bool> {
+		if key != "webLogin" {
+			bail!("{}", strings::invalid_login_key(&key));
+		}
+
+		let Some(token) = cookies.get("token") else {
+			return Ok(false);
+		};
+		settings::set_token(token);
+		Ok(true)
+	}
+}
+
+impl NotificationHandler for Zaimanhua {
+	fn handle_notification(&self, notification: String) {
+		match notification.as_str() {
+			"checkin" => {
+				match net::check_in() {
+					Ok(result) => {
+						settings::set_checkin_result_display(&result);
+						settings::mark_checked_in_today();
+					}
+					Err(_) => settings::set_checkin_result_display("签到失败，请检查登录状态后重试"),
+				}
+				home::refresh_account_info_display();
+			}
+			"resetSettings" => settings::reset_to_defaults(),
+			"refreshAccountInfo" => home::refresh_account_info_display(),
+			"refreshCacheSummary" => settings::refresh_cache_summary_display(),
+			"clearDebugLog" => settings::clear_debug_log(),
+			"clearCache" => {
+				settings::clear_home_cache();
+				settings::refresh_cache_summary_display();
+			}
+			"refreshDailyTasks" => {
+				if let Ok(text) = home::run_daily_tasks() {
+					settings::set_daily_tasks_display(&text);
+				}
+				home::refresh_account_info_display();
+			}
+			"requestSmsCode" => {
+				if let Ok(phone) = settings::get_sms_phone() {
+					_ = net::request_sms_code(&phone);
+				}
+			}
+			"smsLogin" => {
+				if let (Ok(phone), Ok(code)) = (settings::get_sms_phone(), settings::get_sms_code()) {
+					_ = net::login_with_sms(&phone, &code);
+				}
+			}
+			"requestQrCode" => {
+				if let Ok((id, url)) = net::request_qr_token() {
+					settings::set_qr_session_id(&id);
+					settings::set_qr_login_url(&url);
+				}
+			}
+			"pollQrStatus" => {
+				if let Ok(id) = settings::get_qr_session_id() {
+					_ = net::login_with_qr(&id, 1);
+				}
+			}
+			"saveProfile" => {
+				if let Ok(name) = settings::get_profile_name_input() {
+					_ = profiles::save_profile(&name);
+				}
+			}
+			"loadProfile" => {
+				if let Ok(name) = settings::get_profile_name_input() {
+					_ = profiles::load_profile(&name);
+				}
+			}
+			"subscribeComic" => {
+				if settings::is_subscribe_sync_enabled()
+					&& let Ok(comic_id) = settings::get_subscribe_comic_id()
+				{
+					_ = net::subscribe(&comic_id);
+				}
+			}
+			"unsubscribeComic" => {
+				if settings::is_subscribe_sync_enabled()
+					&& let Ok(comic_id) = settings::get_subscribe_comic_id()
+				{
+					_ = net::unsubscribe(&comic_id);
+				}
+			}
+			"runSubscriptionSync" => {
+				if settings::is_subscribe_sync_enabled()
+					&& let Ok(text) = home::run_subscription_sync()
+				{
+					settings::set_sync_result_display(&text);
+				}
+			}
+			"deleteProfile" => {
+				if let Ok(name) = settings::get_profile_name_input() {
+					profiles::delete_profile(&name);
+				}
+			}
+			_ => {}
+		}
+	}
+}
+
+register_source!(
+	Zaimanhua,
+	Home,
+	ListingProvider,
+	DynamicListings,
+	DeepLinkHandler,
+	BasicLoginHandler,
+	WebLoginHandler,
+	NotificationHandler,
+	ImageRequestProvider,
+	PageImageProcessor
+);
@@ -0,0 +1,410 @@
+use crate::settings;
+use aidoku::{
+	Chapter, ContentRating, Manga, MangaStatus, Viewer,
+	alloc::{String, Vec, format},
+	prelude::*,
+	serde::Deserialize,
+};
+
+#[derive(Deserialize)]
+pub struct ApiResponse<T> {
+	pub errno: i32,
+	pub errmsg: String,
+	pub data: Option<T>,
+}
+
+#[derive(Deserialize)]
+pub struct ComicSummary {
+	pub id: String,
+	pub title: String,
+	pub cover: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct SearchData {
+	pub list: Vec<ComicSummary>,
+	pub page: i32,
+	pub size: i32,
+	pub total: i32,
+}
+
+#[derive(Deserialize)]
+pub struct RankItem {
+	pub id: String,
+	pub title: String,
+	pub cover: Option<String>,
+	/// Only present on `rank_type=score` boards.
+	pub score: Option<f32>,
+	/// Only present on `rank_type=roast` boards.
+	pub comment_count: Option<i32>,
+	/// Only present on `rank_type=popular` boards.
+	pub popularity: Option<i64>,
+}
+
+impl From<RankItem> for Manga {
+	fn from(item: RankItem) -> Self {
+		let description = if settings::show_popularity() {
+			item.popularity.map(crate::helpers::format_popularity)
+		} else {
+			None
+		};
+		Manga {
+			key: item.id,
+			title: item.title,
+			cover: item.cover.map(crate::net::apply_cover_quality),
+			description,
+			status: MangaStatus::Unknown,
+			content_rating: ContentRating::Safe,
+			viewer: Viewer::RightToLeft,
+			..Default::default()
+		}
+	}
+}
+
+/// One entry from any of the list-shaped endpoints (filter/rank/category/…). `last_chapter`/
+/// `last_update_time` are only present on chapter-aware endpoints (e.g. `sort=new`); `popularity`
+/// only on rank boards — both are simply absent everywhere else, so one struct covers every shape
+/// instead of needing a dedicated one per endpoint.
+#[derive(Deserialize)]
+pub struct ListItem {
+	pub id: String,
+	pub title: String,
+	pub cover: Option<String>,
+	pub popularity: Option<i64>,
+	pub last_chapter: Option<String>,
+	pub last_update_time: Option<i64>,
+}
+
+impl From<ListItem> for Manga {
+	fn from(item: ListItem) -> Self {
+		let description = match (item.last_chapter, item.last_update_time) {
+			(Some(last_chapter), Some(timestamp)) => Some(format!(
+				"更新至{last_chapter} · {}",
+				crate::helpers::format_relative_time(timestamp)
+			)),
+			(Some(last_chapter), None) => Some(format!("更新至{last_chapter}")),
+			(None, _) if settings::show_popularity() => {
+				item.popularity.map(crate::helpers::format_popularity)
+			}
+			(None, _) => None,
+		};
+		let url = Some(crate::net::manga_url(&item.id));
+		Manga {
+			key: item.id,
+			title: item.title,
+			cover: item.cover.map(crate::net::apply_cover_quality),
+			description,
+			status: MangaStatus::Unknown,
+			url,
+			content_rating: ContentRating::Safe,
+			viewer: Viewer::RightToLeft,
+			..Default::default()
+		}
+	}
+}
+
+#[derive(Deserialize)]
+pub struct ListData {
+	pub list: Vec<ListItem>,
+	pub page: i32,
+	pub size: i32,
+	pub total: i32,
+}
+
+#[derive(Deserialize)]
+pub struct CategoryItem {
+	pub id: String,
+	pub name: String,
+}
+
+#[derive(Deserialize)]
+pub struct CategoryData {
+	pub list: Vec<CategoryItem>,
+}
+
+#[derive(Deserialize)]
+pub struct HistoryItem {
+	pub comic_id: String,
+	pub comic_title: String,
+	pub comic_cover: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct HistoryData {
+	pub list: Vec<HistoryItem>,
+	pub page: i32,
+	pub size: i32,
+	pub total: i32,
+}
+
+impl From<HistoryItem> for Manga {
+	fn from(item: HistoryItem) -> Self {
+		let url = Some(crate::net::manga_url(&item.comic_id));
+		Manga {
+			key: item.comic_id,
+			title: item.comic_title,
+			cover: item.comic_cover.map(crate::net::apply_cover_quality),
+			status: MangaStatus::Unknown,
+			url,
+			content_rating: ContentRating::Safe,
+			viewer: Viewer::RightToLeft,
+			..Default::default()
+		}
+	}
+}
+
+/// One editorial category from `recommend/list` (e.g. the id-109 banner, 条漫专区, 热门连载, …),
+/// each carrying its own curated manga list.
+#[derive(Deserialize)]
+pub struct RecommendCategory {
+	pub category_id: i32,
+	pub category_name: String,
+	pub list: Vec<ComicSummary>,
+}
+
+/// An entry from the reading-record API, i.e. a series with unread progress.
+#[derive(Deserialize)]
+pub struct RecordItem {
+	pub comic_id: String,
+	pub comic_title: String,
+	pub comic_cover: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct RecordData {
+	pub list: Vec<RecordItem>,
+	pub page: i32,
+	pub size: i32,
+	pub total: i32,
+}
+
+impl From<RecordItem> for Manga {
+	fn from(item: RecordItem) -> Self {
+		let url = Some(crate::net::manga_url(&item.comic_id));
+		Manga {
+			key: item.comic_id,
+			title: item.comic_title,
+			cover: item.comic_cover.map(crate::net::apply_cover_quality),
+			status: MangaStatus::Unknown,
+			url,
+			content_rating: ContentRating::Safe,
+			viewer: Viewer::RightToLeft,
+			..Default::default()
+		}
+	}
+}
+
+impl From<ComicSummary> for Manga {
+	fn from(item: ComicSummary) -> Self {
+		let url = Some(crate::net::manga_url(&item.id));
+		Manga {
+			key: item.id,
+			title: item.title,
+			cover: item.cover.map(crate::net::apply_cover_quality),
+			status: MangaStatus::Unknown,
+			url,
+			content_rating: ContentRating::Safe,
+			viewer: Viewer::RightToLeft,
+			..Default::default()
+		}
+	}
+}
+
+#[derive(Deserialize)]
+pub struct ChapterItem {
+	pub chapter_id: String,
+	pub chapter_title: Option<String>,
+	#[serde(default)]
+	pub is_vip: i32,
+}
+
+#[derive(Deserialize)]
+pub struct ChapterGroup {
+	pub title: Option<String>,
+	pub chapters: Vec<ChapterItem>,
+}
+
+/// Typed counterpart of the manga detail + chapter list endpoint, wired to
+/// `Source::get_manga_update`.
+#[derive(Deserialize)]
+pub struct MangaDetail {
+	pub id: String,
+	pub title: String,
+	pub original_title: Option<String>,
+	pub cover: Option<String>,
+	pub description: Option<String>,
+	pub author: Option<Vec<String>>,
+	pub theme: Option<Vec<String>>,
+	pub status: Option<i32>,
+	/// Pinyin slug used in the site's own chapter URLs; not every series has one.
+	pub py: Option<String>,
+	pub chapter_group: Vec<ChapterGroup>,
+}
+
+impl MangaDetail {
+	pub fn into_manga(self) -> Manga {
+		let status = zh_common::ongoing_completed_status(self.status);
+		let update_strategy = zh_common::update_strategy(status);
+		let url = Some(crate::net::manga_url(&self.id));
+		let content_rating =
+			crate::helpers::content_rating_from_tags(self.theme.as_deref().unwrap_or_default());
+		let viewer = crate::helpers::viewer_from_tags(self.theme.as_deref().unwrap_or_default());
+		let (title, description) =
+			crate::helpers::resolve_title(self.title, self.original_title, self.description);
+
+		Manga {
+			key: self.id,
+			title,
+			cover: self.cover,
+			authors: self.author,
+			description,
+			tags: self.theme,
+			status,
+			update_strategy,
+			url,
+			content_rating,
+			viewer,
+			..Default::default()
+		}
+	}
+
+	pub fn into_chapters(&self) -> Vec<Chapter> {
+		let mut groups = self.chapter_group.iter().collect::<Vec<_>>();
+		let preference = settings::get_group_preference();
+		if !preference.is_empty() {
+			groups.sort_by_key(|group| {
+				let name = group.title.as_deref().unwrap_or_default();
+				preference.iter().position(|p| p == name).unwrap_or(preference.len())
+			});
+		}
+
+		let hidden_keywords = settings::get_hidden_group_keywords();
+		groups.retain(|group| {
+			let name = group.title.as_deref().unwrap_or_default();
+			!hidden_keywords.iter().any(|keyword| name.contains(keyword.as_str()))
+		});
+
+		let cleanup_enabled = settings::is_chapter_title_cleanup_enabled();
+		let comic_py = self.py.as_deref().unwrap_or(self.id.as_str());
+
+		// Used as the index-based fallback below, over the flattened chapter count rather than each
+		// group's own length — two series at the same relative position in different groups would
+		// otherwise get the same fallback number and collide in the dedup pass further down.
+		let total: usize = groups.iter().map(|group| group.chapters.len()).sum();
+		let mut flat_idx = 0usize;
+
+		let mut chapters = Vec::new();
+		for (group_idx, group) in groups.iter().enumerate() {
+			let scanlator = group.title.clone();
+			let scanlators = crate::helpers::scanlators_for_group(scanlator, groups.len());
+			let volume_number = Some(group_idx as f32 + 1.0);
+			for item in &group.chapters {
+				let chapter_number = item
+					.chapter_title
+					.as_deref()
+					.and_then(parse_chapter_number_from_title)
+					.or(Some((total - flat_idx) as f32));
+				flat_idx += 1;
+				let title = if cleanup_enabled {
+					item.chapter_title
+						.as_deref()
+						.map(|title| crate::helpers::clean_chapter_title(title, &self.title))
+				} else {
+					item.chapter_title.clone()
+				};
+				let locked = item.is_vip == 1;
+				if locked && settings::hide_locked_chapters() {
+					continue;
+				}
+				let title = match title {
+					Some(title) if locked => Some(format!("🔒 {title}")),
+					title => title,
+				};
+
+				let url = Some(format!(
+					"{}/view/{comic_py}/{}/{}",
+					crate::net::base_url(),
+					self.id,
+					item.chapter_id
+				));
+
+				chapters.push(Chapter {
+					key: item.chapter_id.clone(),
+					title,
+					volume_number,
+					chapter_number,
+					scanlators: scanlators.clone(),
+					locked,
+					url,
+					..Default::default()
+				});
+			}
+		}
+
+		let mut seen = Vec::new();
+		chapters.retain(|chapter| {
+			let key = match chapter.chapter_number {
+				Some(number) => format!("n:{number}"),
+				None => format!(
+					"t:{}",
+					chapter.title.as_deref().unwrap_or_default().trim().to_lowercase()
+				),
+			};
+			if seen.contains(&key) {
+				false
+			} else {
+				seen.push(key);
+				true
+			}
+		});
+
+		if settings::is_chapter_order_oldest_first() {
+			chapters.reverse();
+		}
+
+		chapters
+	}
+}
+
+/// Typed counterpart to the raw `serde_json::Value` `home.rs` reads `net::get_user_info` through
+/// directly for the VIP/points/streak/binding fields it already used; this adds the richer fields
+/// (nickname, avatar, registration date, VIP expiry) needed for `home::format_account_info`.
+#[derive(Deserialize)]
+pub struct UserInfo {
+	pub nickname: Option<String>,
+	/// This `aidoku` version's settings UI has no image-display setting type (checked against
+	/// every `type` used in every source's `settings.json` in this workspace) — there's nowhere
+	/// to actually render this, so it's only surfaced as a URL in the account info text.
+	pub avatar: Option<String>,
+	pub mobile: Option<String>,
+	pub email: Option<String>,
+	#[serde(default)]
+	pub status: i32,
+	#[serde(default)]
+	pub vip: i32,
+	#[serde(default)]
+	pub point: i64,
+	#[serde(default)]
+	pub sign_days: i64,
+	pub register_time: Option<i64>,
+	pub vip_expire_time: Option<i64>,
+}
+
+/// Parses a chapter number out of titles like "第12话", "第3卷" or a bare "012", returning `None`
+/// for anything else (e.g. a pure story title) so callers can fall back to index-based numbering.
+fn parse_chapter_number_from_title(title: &str) -> Option<f32> {
+	let trimmed = title.trim();
+	if let Some(rest) = trimmed.strip_prefix('第') {
+		let digits: String = rest.chars().take_while(|c| c.is_ascii_digit() || *c == '.').collect();
+		if !digits.is_empty() {
+			return digits.parse().ok();
+		}
+	}
+	if !trimmed.is_empty() && trimmed.chars().all(|c| c.is_ascii_digit()) {
+		return trimmed.parse().ok();
+	}
+	None
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,126 @@
+#![expect(clippy::unwrap_used)]
+
+use super::*;
+use aidoku::UpdateStrategy;
+use aidoku_test::aidoku_test;
+
+#[aidoku_test]
+fn list_item_into_manga_plain() {
+	let data: ListData = serde_json::from_str(
+		r#"{"list":[{"id":"12345","title":"测试漫画","cover":"https://example.com/cover.jpg"}],"page":1,"size":20,"total":1}"#,
+	)
+	.unwrap();
+
+	let manga: Manga = data.list.into_iter().next().unwrap().into();
+	assert_eq!(
+		manga,
+		Manga {
+			key: "12345".into(),
+			title: "测试漫画".into(),
+			cover: Some("https://example.com/cover.jpg".into()),
+			status: MangaStatus::Unknown,
+			url: Some("https://manhua.zaimanhua.com/view/12345".into()),
+			content_rating: ContentRating::Safe,
+			viewer: Viewer::RightToLeft,
+			..Default::default()
+		}
+	);
+}
+
+/// `last_chapter`/`last_update_time` only show up on chapter-aware list endpoints (e.g.
+/// `sort=new`); when present they take over the description instead of the popularity line.
+#[aidoku_test]
+fn list_item_into_manga_with_chapter() {
+	let data: ListData = serde_json::from_str(
+		r#"{"list":[{"id":"1","title":"连载中","cover":null,"last_chapter":"第10话","last_update_time":1700000000}],"page":1,"size":20,"total":1}"#,
+	)
+	.unwrap();
+
+	let manga: Manga = data.list.into_iter().next().unwrap().into();
+	assert_eq!(manga.key, "1");
+	assert!(manga.description.unwrap().starts_with("更新至第10话"));
+}
+
+#[aidoku_test]
+fn manga_detail_into_manga() {
+	let detail: MangaDetail = serde_json::from_str(
+		r#"{"id":"555","title":"完结漫画","original_title":null,"cover":"https://example.com/c.jpg","description":"简介","author":["作者甲"],"theme":["百合"],"status":1,"py":"wanjiemanhua","chapter_group":[]}"#,
+	)
+	.unwrap();
+
+	let manga = detail.into_manga();
+	assert_eq!(
+		manga,
+		Manga {
+			key: "555".into(),
+			title: "完结漫画".into(),
+			cover: Some("https://example.com/c.jpg".into()),
+			authors: Some(["作者甲".into()].into()),
+			description: Some("简介".into()),
+			tags: Some(["百合".into()].into()),
+			status: MangaStatus::Completed,
+			update_strategy: UpdateStrategy::Never,
+			url: Some("https://manhua.zaimanhua.com/view/555".into()),
+			content_rating: ContentRating::Suggestive,
+			viewer: Viewer::RightToLeft,
+			..Default::default()
+		}
+	);
+}
+
+/// `content_rating_from_tags` already upgrades `Safe` to `Suggestive`/`NSFW` off a series' theme
+/// tags (see `manga_detail_into_manga` above for the `Suggestive` case) — this covers the `NSFW`
+/// keyword list on the same code path, since it's `MangaDetail::into_manga`'s only caller.
+#[aidoku_test]
+fn manga_detail_into_manga_nsfw_tag() {
+	let detail: MangaDetail = serde_json::from_str(
+		r#"{"id":"556","title":"成人向漫画","original_title":null,"cover":null,"description":null,"author":null,"theme":["成人"],"status":0,"py":null,"chapter_group":[]}"#,
+	)
+	.unwrap();
+
+	assert_eq!(detail.into_manga().content_rating, ContentRating::NSFW);
+}
+
+/// `viewer_from_tags` upgrades the default `RightToLeft` to `Webtoon` for series tagged "条漫"
+/// (this API's own term for a vertical-scroll strip comic — see `recommend/list`'s "条漫专区"
+/// editorial category), since the detail endpoint has no dedicated reading-direction field.
+#[aidoku_test]
+fn manga_detail_into_manga_webtoon_tag() {
+	let detail: MangaDetail = serde_json::from_str(
+		r#"{"id":"557","title":"条漫连载","original_title":null,"cover":null,"description":null,"author":null,"theme":["条漫"],"status":0,"py":null,"chapter_group":[]}"#,
+	)
+	.unwrap();
+
+	assert_eq!(detail.into_manga().viewer, Viewer::Webtoon);
+}
+
+#[aidoku_test]
+fn manga_detail_into_chapters() {
+	let detail: MangaDetail = serde_json::from_str(
+		r#"{"id":"555","title":"完结漫画","original_title":null,"cover":null,"description":null,"author":null,"theme":null,"status":1,"py":null,"chapter_group":[{"title":"连载","chapters":[{"chapter_id":"c1","chapter_title":"第1话"},{"chapter_id":"c2","chapter_title":"第2话"}]}]}"#,
+	)
+	.unwrap();
+
+	let chapters = detail.into_chapters();
+	assert_eq!(chapters.len(), 2);
+	assert_eq!(chapters[0].key, "c1");
+	assert_eq!(chapters[0].chapter_number, Some(1.0));
+	assert_eq!(chapters[1].key, "c2");
+	assert_eq!(chapters[1].chapter_number, Some(2.0));
+}
+
+/// Two groups each with a non-numeric (番外) title land on index 0 of their own group, so a
+/// per-group fallback number would assign both the same chapter_number and the cross-group dedup
+/// pass would then drop one as a false duplicate. The fallback has to be computed over the
+/// flattened chapter list so these two distinct chapters both survive.
+#[aidoku_test]
+fn manga_detail_into_chapters_fallback_number_across_groups() {
+	let detail: MangaDetail = serde_json::from_str(
+		r#"{"id":"555","title":"完结漫画","original_title":null,"cover":null,"description":null,"author":null,"theme":null,"status":1,"py":null,"chapter_group":[{"title":"正篇","chapters":[{"chapter_id":"c1","chapter_title":"番外一"}]},{"title":"番外","chapters":[{"chapter_id":"c2","chapter_title":"番外二"}]}]}"#,
+	)
+	.unwrap();
+
+	let chapters = detail.into_chapters();
+	assert_eq!(chapters.len(), 2);
+	assert_eq!(chapters.iter().map(|c| &c.key).collect::<Vec<_>>(), vec!["c1", "c2"]);
+}
@@ -0,0 +1,666 @@
+use aidoku::{
+	Result,
+	alloc::{String, Vec, format, string::ToString},
+	bail, error,
+	imports::{
+		net::{Request, Response},
+		std::current_date,
+	},
+	prelude::*,
+};
+
+pub const BASE_URL: &str = "https://manhua.zaimanhua.com";
+pub const API_URL: &str = "https://v4api.zaimanhua.com";
+pub const DEFAULT_IMAGE_HOST: &str = "images.zaimanhua.com";
+
+/// A known-working fallback line, paired API+web host, for regions where the default is blocked.
+const MIRROR_API_URL: &str = "https://v4api2.zaimanhua.com";
+const MIRROR_BASE_URL: &str = "https://m2.zaimanhua.com";
+
+const USER_AGENT: &str = "Mozilla/5.0 (Linux; Android 13) zaimanhua/4.0.0";
+
+/// Claimed app channel/version for [`apply_signature_headers`] — matches the version already
+/// baked into [`USER_AGENT`].
+const APP_CHANNEL: &str = "official";
+const APP_VERSION: &str = "4.0.0";
+
+/// Appends a speculative reproduction of the official app's channel/version/timestamp/sign
+/// headers, when `settings::is_signature_headers_enabled` is on (off by default). The actual
+/// signing secret and algorithm aren't published anywhere, and this crate has no captured app
+/// traffic to reverse-engineer them from (checked — there's no fixture or sample payload anywhere
+/// in this source), so `sign` here is only a best-effort md5 of the other fields, not a verified
+/// reproduction of what the app actually sends. Toggle this on to see if a restricted endpoint
+/// responds with more data; toggle it back off if it doesn't help.
+fn apply_signature_headers(request: Result<Request>) -> Result<Request> {
+	if !crate::settings::is_signature_headers_enabled() {
+		return request;
+	}
+	let timestamp = current_date();
+	let sign = zh_common::md5_hex(format!("{APP_CHANNEL}{APP_VERSION}{timestamp}"));
+	request
+		.header("channel", APP_CHANNEL)
+		.header("version", APP_VERSION)
+		.header("timestamp", &timestamp.to_string())
+		.header("sign", &sign)
+}
+
+/// The `customUserAgent` override if one is set, else the hardcoded [`USER_AGENT`]. Every request
+/// this crate sends — API calls in this file, image requests in `lib.rs`'s `get_image_request` —
+/// goes through this instead of the literal constant, for users whose network fingerprints or
+/// blocks the default string.
+pub fn user_agent() -> String {
+	crate::settings::get_custom_user_agent().unwrap_or_else(|| USER_AGENT.to_string())
+}
+
+/// Appends the user's `customExtraHeaders` overrides (advanced setting, one `"Header-Name: value"`
+/// pair per line) to a request, for endpoints that start rejecting the default header set.
+/// Malformed lines (no `:` separator) are skipped rather than failing the whole request over one
+/// typo — see `settings::get_extra_headers`.
+pub fn apply_custom_headers(request: Result<Request>) -> Result<Request> {
+	crate::settings::get_extra_headers()
+		.into_iter()
+		.fold(request, |request, (name, value)| request.header(&name, &value))
+}
+
+/// Resolves the v4 API base URL per the "apiLine" setting (the hardcoded default, the known
+/// mirror, or a user-supplied custom host), so a regional block or domain change doesn't require
+/// a source rebuild. Every call site that used to format [`API_URL`] directly now goes through
+/// this instead.
+pub fn api_url() -> String {
+	match crate::settings::get_api_line().as_str() {
+		"mirror" => MIRROR_API_URL.to_string(),
+		"custom" => crate::settings::get_custom_api_url().unwrap_or_else(|| API_URL.to_string()),
+		// The user hasn't picked a line themselves — defer to automatic failover, if active.
+		_ if crate::settings::is_mirror_failover_active() => MIRROR_API_URL.to_string(),
+		_ => API_URL.to_string(),
+	}
+}
+
+/// The [`BASE_URL`] counterpart to [`api_url`] — switched together with it, since the official
+/// app always pairs a given API host with a matching web host.
+pub fn base_url() -> String {
+	match crate::settings::get_api_line().as_str() {
+		"mirror" => MIRROR_BASE_URL.to_string(),
+		"custom" => crate::settings::get_custom_web_url().unwrap_or_else(|| BASE_URL.to_string()),
+		_ if crate::settings::is_mirror_failover_active() => MIRROR_BASE_URL.to_string(),
+		_ => BASE_URL.to_string(),
+	}
+}
+
+/// The web URL for a comic's detail page, for every `Manga.url` this crate sets — list-shaped
+/// results (search/filter/rank/subscribe/…) as well as [`models::MangaDetail::into_manga`](
+/// crate::models::MangaDetail::into_manga).
+pub fn manga_url(comic_id: &str) -> String {
+	format!("{}/view/{comic_id}", base_url())
+}
+
+/// Typed endpoint builders for the v4 API, so call sites stop hand-assembling query strings with
+/// `format!`. Unauthenticated endpoints (search/filter/rank) return a full URL, ready for
+/// `Request::get`/`helpers::fetch_list`; authenticated ones (detail/chapter/sub/userInfo/sign)
+/// return the host-relative path `fetch_authed_json`/`fetch_authed`/`get_api_request` already
+/// expect, except `sign_in` which is called directly with its own `Authorization` header and so
+/// needs the host prefixed itself. Covers the endpoints actually exercised today; home.rs's own
+/// section/recommend URLs and the login/QR/SMS flow in this file aren't migrated, since they're
+/// one-off enough that a shared builder wouldn't save anything over the inline `format!` already
+/// there.
+pub mod urls {
+	use super::api_url;
+	use aidoku::alloc::{String, format};
+
+	pub fn search(keyword: &str, page: i32, size: i32) -> String {
+		format!("{}/app/v1/search/comic?keyword={keyword}&page={page}&size={size}", api_url())
+	}
+
+	pub fn search_by_author(author: &str, page: i32, size: i32) -> String {
+		format!("{}/app/v1/search/comic?author={author}&page={page}&size={size}", api_url())
+	}
+
+	/// `query` is the filter-specific part, e.g. `"sort=recommend"` or `"audience=shounen"`; `None`
+	/// for the plain unfiltered listing.
+	pub fn filter(query: Option<&str>, page: i32, size: i32) -> String {
+		match query {
+			Some(query) => format!("{}/app/v1/comic/filter?{query}&page={page}&size={size}", api_url()),
+			None => format!("{}/app/v1/comic/filter?page={page}&size={size}", api_url()),
+		}
+	}
+
+	pub fn rank(by_time: &str, rank_type: &str, page: i32, size: i32) -> String {
+		format!(
+			"{}/app/v1/comic/rank?by_time={by_time}&rank_type={rank_type}&page={page}&size={size}",
+			api_url()
+		)
+	}
+
+	pub fn detail(comic_id: &str) -> String {
+		format!("/app/v1/comic/detail/{comic_id}")
+	}
+
+	pub fn chapter(comic_id: &str, chapter_id: &str) -> String {
+		format!("/app/v1/comic/chapter/{comic_id}/{chapter_id}")
+	}
+
+	pub fn sub_add(comic_id: &str) -> String {
+		format!("/app/v1/comic/sub/add?comic_id={comic_id}")
+	}
+
+	pub fn sub_cancel(comic_id: &str) -> String {
+		format!("/app/v1/comic/sub/cancel?comic_id={comic_id}")
+	}
+
+	pub fn sub_list(page: i32, size: i32) -> String {
+		format!("/app/v1/comic/sub/list?page={page}&size={size}")
+	}
+
+	pub fn user_info() -> &'static str {
+		"/app/v1/user/info"
+	}
+
+	pub fn sign_in() -> String {
+		format!("{}/app/v1/sign/in", api_url())
+	}
+}
+
+/// Records `url`, the errno it returned (if any) and how long the round-trip took (second
+/// resolution — there's no sub-second clock import in this `aidoku` version, checked against
+/// every other source in this workspace) into `debugLogDisplay`, when "调试日志" is on. Called
+/// around the request helpers call sites actually go through, plus `home.rs`'s parallel
+/// `send_all` fan-out which bypasses them.
+pub fn debug_log(url: &str, errno: Option<i64>, elapsed_secs: i64) {
+	if !crate::settings::is_debug_logging_enabled() {
+		return;
+	}
+	let errno = errno.map(|e| e.to_string()).unwrap_or_else(|| "?".to_string());
+	crate::settings::append_debug_log(&format!("{url} · errno={errno} · {elapsed_secs}s"));
+}
+
+pub use zh_common::errno::ErrorKind;
+
+fn errno_lang() -> zh_common::errno::Lang {
+	match crate::strings::current() {
+		crate::strings::Lang::Zh => zh_common::errno::Lang::Zh,
+		crate::strings::Lang::En => zh_common::errno::Lang::En,
+	}
+}
+
+/// The error taxonomy and `{"errno": ..., "errmsg": ...}` envelope check used by this source live
+/// in `zh-common`; wrapped here so the many existing call sites (`net::check_errno`) keep their
+/// same one-argument shape while still getting the reader's `appearanceLanguage` choice, instead
+/// of the shared crate's Chinese-only default.
+pub fn check_errno(json: &serde_json::Value) -> Result<serde_json::Value> {
+	zh_common::errno::check_errno(json, errno_lang())
+}
+
+/// Typed counterpart to [`check_errno`], for call sites that already deserialized straight into a
+/// `models::ApiResponse<T>` envelope instead of working with raw JSON.
+pub fn check_api_response<T>(response: crate::models::ApiResponse<T>) -> Result<T> {
+	let lang = errno_lang();
+	zh_common::errno::check_api_response(response.errno, &response.errmsg, response.data, lang)
+}
+
+pub fn login() -> Result<String> {
+	let username = crate::settings::get_username()?;
+	// Already an md5 hash — `settings::get_password` never hands back the plaintext password.
+	let hashed = crate::settings::get_password()?;
+	let api_url = api_url();
+	let url = format!("{api_url}/app/v1/user/login?username={username}&password={hashed}");
+
+	let mut response = Request::post(url)?.header("User-Agent", &user_agent()).send()?;
+	let json: serde_json::Value = response.get_json()?;
+	let data = check_errno(&json)?;
+	let token = data
+		.get("token")
+		.and_then(|v| v.as_str())
+		.ok_or_else(|| error!("No token in login response"))?;
+
+	if let Some(refresh_token) = data.get("refresh_token").and_then(|v| v.as_str()) {
+		crate::settings::set_refresh_token(refresh_token);
+	}
+	crate::settings::set_token(token);
+	Ok(token.to_string())
+}
+
+/// Exchanges a stored refresh token for a fresh session token, without resending the account's
+/// username/password — the closest thing this API has to a silent re-login. Only works if
+/// [`login`] has previously received a `refresh_token` to store; fails immediately otherwise so
+/// callers can fall back to a full [`login`].
+pub fn try_refresh_token() -> Result<String> {
+	let refresh_token = crate::settings::get_refresh_token().ok_or_else(|| error!("No refresh token stored"))?;
+	let api_url = api_url();
+	let url = format!("{api_url}/app/v1/user/token/refresh?refresh_token={refresh_token}");
+	let mut response = Request::post(url)?.header("User-Agent", &user_agent()).send()?;
+	let json: serde_json::Value = response.get_json()?;
+	let data = check_errno(&json)?;
+	let token = data
+		.get("token")
+		.and_then(|v| v.as_str())
+		.ok_or_else(|| error!("No token in refresh response"))?;
+
+	if let Some(refresh_token) = data.get("refresh_token").and_then(|v| v.as_str()) {
+		crate::settings::set_refresh_token(refresh_token);
+	}
+	crate::settings::set_token(token);
+	Ok(token.to_string())
+}
+
+/// Requests an SMS verification code for the given phone number, for accounts that log in by
+/// code instead of a password (see `settings::get_sms_phone`).
+pub fn request_sms_code(phone: &str) -> Result<()> {
+	let api_url = api_url();
+	let url = format!("{api_url}/app/v1/user/sms/send?mobile={phone}");
+	let mut response = Request::post(url)?.header("User-Agent", &user_agent()).send()?;
+	let json: serde_json::Value = response.get_json()?;
+	check_errno(&json)?;
+	Ok(())
+}
+
+/// Logs in with a phone number and the SMS code it received, as an alternative to [`login`]'s
+/// username/password flow.
+pub fn login_with_sms(phone: &str, code: &str) -> Result<String> {
+	let api_url = api_url();
+	let url = format!("{api_url}/app/v1/user/login/sms?mobile={phone}&code={code}");
+	let mut response = Request::post(url)?.header("User-Agent", &user_agent()).send()?;
+	let json: serde_json::Value = response.get_json()?;
+	let data = check_errno(&json)?;
+	let token = data
+		.get("token")
+		.and_then(|v| v.as_str())
+		.ok_or_else(|| error!("No token in login response"))?;
+
+	crate::settings::set_token(token);
+	Ok(token.to_string())
+}
+
+/// Requests a fresh QR login session: a short-lived id to poll and the URL the id is embedded
+/// in, which the official app can open directly when scanned.
+pub fn request_qr_token() -> Result<(String, String)> {
+	let mut response = Request::post(format!("{}/app/v1/user/qrcode/create", api_url()))?
+		.header("User-Agent", &user_agent())
+		.send()?;
+	let json: serde_json::Value = response.get_json()?;
+	let data = check_errno(&json)?;
+	let id = data
+		.get("qrcode_id")
+		.and_then(|v| v.as_str())
+		.ok_or_else(|| error!("Missing qrcode.qrcode_id"))?
+		.to_string();
+	let url = data
+		.get("qrcode_url")
+		.and_then(|v| v.as_str())
+		.ok_or_else(|| error!("Missing qrcode.qrcode_url"))?
+		.to_string();
+	Ok((id, url))
+}
+
+/// Polls a QR session once: `Ok(None)` means it hasn't been scanned and confirmed yet, `Ok(Some)`
+/// is the resulting token.
+fn poll_qr_status(qr_id: &str) -> Result<Option<String>> {
+	let mut response = Request::get(format!("{}/app/v1/user/qrcode/poll?id={qr_id}", api_url()))?
+		.header("User-Agent", &user_agent())
+		.send()?;
+	let json: serde_json::Value = response.get_json()?;
+	// errno 100 means "not confirmed yet" — distinct from an actual failure.
+	if json.get("errno").and_then(|v| v.as_i64()) == Some(100) {
+		return Ok(None);
+	}
+	let data = check_errno(&json)?;
+	Ok(data.get("token").and_then(|v| v.as_str()).map(ToString::to_string))
+}
+
+/// Polls a QR session up to `attempts` times, immediately (there's no sleep primitive available
+/// in this wasm sandbox, so this is a tight poll rather than a timed one), bailing with a clear
+/// message if it's still unconfirmed once attempts run out.
+pub fn login_with_qr(qr_id: &str, attempts: u32) -> Result<String> {
+	for _ in 0..attempts.max(1) {
+		if let Some(token) = poll_qr_status(qr_id)? {
+			crate::settings::set_token(&token);
+			return Ok(token);
+		}
+	}
+	bail!("{}", crate::strings::qr_not_confirmed())
+}
+
+/// Invalidates the current token server-side, so it can't keep being used if the device was
+/// compromised after the user logs out locally. Best-effort: called right before the local
+/// account state is cleared either way.
+pub fn logout() -> Result<()> {
+	let token = crate::settings::get_token().ok_or_else(|| error!("Not logged in"))?;
+	Request::post(format!("{}/app/v1/user/logout", api_url()))?
+		.header("User-Agent", &user_agent())
+		.header("Authorization", &token)
+		.send()?;
+	Ok(())
+}
+
+/// Builds an authenticated GET request against the v4 API, logging in first if needed.
+pub fn get_api_request(path: &str) -> Result<Request> {
+	let token = match crate::settings::get_token() {
+		Some(token) => token,
+		None => login()?,
+	};
+
+	let url = format!("{}{path}", api_url());
+	apply_signature_headers(apply_custom_headers(
+		Request::get(url)?
+			.header("User-Agent", &user_agent())
+			.header("Authorization", &token),
+	))
+}
+
+/// How long [`fetch_deduped_json`]'s memoized responses stay fresh before a call falls through to
+/// a real request again.
+const DEDUPE_TTL_SECONDS: i64 = 5;
+
+/// Memoizes `fetch`'s result under `cache_key` for [`DEDUPE_TTL_SECONDS`], so endpoints called more
+/// than once in quick succession during the same update pass (see
+/// `settings::get_recent_response`'s doc comment for the two call sites this covers) only actually
+/// hit the network once.
+pub(crate) fn fetch_deduped_json(
+	cache_key: &str,
+	fetch: impl FnOnce() -> Result<serde_json::Value>,
+) -> Result<serde_json::Value> {
+	if let Some(cached) = crate::settings::get_recent_response(cache_key, DEDUPE_TTL_SECONDS)
+		&& let Ok(value) = serde_json::from_str(&cached)
+	{
+		return Ok(value);
+	}
+	let value = fetch()?;
+	if let Ok(raw) = serde_json::to_string(&value) {
+		crate::settings::cache_recent_response(cache_key, &raw);
+	}
+	Ok(value)
+}
+
+/// Fetches the logged-in user's account info, including today's check-in state and streak.
+pub fn get_user_info() -> Result<serde_json::Value> {
+	let json = fetch_deduped_json("userInfo", || {
+		fetch_authed_json(urls::user_info(), crate::settings::get_retry_attempts())
+	})?;
+	check_errno(&json)
+}
+
+/// Typed counterpart to [`get_user_info`], for call sites that want the richer
+/// `crate::models::UserInfo` fields (nickname, avatar, registration date, VIP expiry) instead of
+/// picking individual fields out of the raw JSON by hand.
+pub fn get_typed_user_info() -> Result<crate::models::UserInfo> {
+	let json: crate::models::ApiResponse<crate::models::UserInfo> =
+		fetch_authed(urls::user_info(), crate::settings::get_retry_attempts())?;
+	check_api_response(json)
+}
+
+/// POSTs the chapter a reader just opened to the account's reading-record endpoint, so progress
+/// made in Aidoku shows up in the official app/site too. Silently does nothing when logged out —
+/// this is best-effort syncing, not something that should block the page list from loading.
+pub fn sync_read_progress(comic_id: &str, chapter_id: &str) -> Result<()> {
+	if !crate::settings::is_reading_history_upload_enabled() {
+		return Ok(());
+	}
+	let Some(token) = crate::settings::get_token() else {
+		return Ok(());
+	};
+	let body = serde_json::json!({
+		"comic_id": comic_id,
+		"chapter_id": chapter_id,
+		"read_time": current_date(),
+	})
+	.to_string();
+	Request::post(format!("{}/app/v1/user/record/save", api_url()))?
+		.header("User-Agent", &user_agent())
+		.header("Authorization", &token)
+		.header("Content-Type", "application/json")
+		.body(body)
+		.send()?;
+	Ok(())
+}
+
+/// Swaps `DEFAULT_IMAGE_HOST` for the user's configured mirror, when one is set. Some networks
+/// block the default CDN host outright, so this is the one place every image URL passes through
+/// before it's handed to the reader.
+pub fn rewrite_image_host(url: &str) -> String {
+	match crate::settings::get_image_host_mirror() {
+		Some(mirror) => url.replace(DEFAULT_IMAGE_HOST, &mirror),
+		None => url.to_string(),
+	}
+}
+
+/// Distinguishes the two common page-list failure modes — the chapter is VIP-gated, or the
+/// request needs to be authenticated — from the generic errno/errmsg envelope, so the reader sees
+/// a specific, actionable reason instead of a raw API error code.
+///
+/// `fallback_url` (the chapter's own web page, when known) is appended to the genuinely
+/// access-gated cases so the reader has something to do about it: this `aidoku` version has no
+/// webview `PageContent` variant to embed that page in-app, so surfacing the link in the error
+/// text is the best available fallback.
+pub fn check_chapter_access(json: &serde_json::Value, fallback_url: Option<&str>) -> Result<serde_json::Value> {
+	match json.get("errno").and_then(|v| v.as_i64()).unwrap_or(-1) {
+		401 if crate::settings::get_token().is_none() => {
+			bail!("{}", crate::strings::chapter_needs_login(fallback_url))
+		}
+		401 => bail!("{}", crate::strings::chapter_session_expired()),
+		1001 => bail!("{}", crate::strings::chapter_paid(fallback_url)),
+		_ => check_errno(json),
+	}
+}
+
+/// Sends an authenticated GET and parses its JSON envelope, recovering and retrying once if the
+/// server reports errno 99 (expired token) — the single place every authed call site goes through
+/// so an expired session recovers on its own instead of failing outright. Prefers
+/// [`try_refresh_token`] over a full [`login`], so recovering from an expired token doesn't
+/// transmit the account's username/password unless there's no refresh token to use instead.
+pub fn fetch_authed_json(path: &str, attempts: u32) -> Result<serde_json::Value> {
+	let started_at = current_date();
+	let mut response = send_with_retry(|| get_api_request(path), attempts)?;
+	let mut json: serde_json::Value = response.get_json()?;
+	if json.get("errno").and_then(|v| v.as_i64()) == Some(99) {
+		if try_refresh_token().is_err() {
+			login()?;
+		}
+		response = send_with_retry(|| get_api_request(path), attempts)?;
+		json = response.get_json()?;
+	}
+	debug_log(path, json.get("errno").and_then(|v| v.as_i64()), current_date() - started_at);
+	Ok(json)
+}
+
+/// Generic counterpart to [`fetch_authed_json`] for call sites that deserialize straight into a
+/// typed envelope (e.g. `models::ApiResponse<T>`) instead of working with raw JSON.
+pub fn fetch_authed<T: serde::de::DeserializeOwned>(path: &str, attempts: u32) -> Result<T> {
+	let json = fetch_authed_json(path, attempts)?;
+	serde_json::from_value(json).map_err(|_| error!("Invalid response"))
+}
+
+/// Retries a request up to `attempts` times total before giving up (see
+/// `settings::get_retry_attempts`, which most call sites now pass through), for zaimanhua's CDN
+/// occasionally failing transiently under load. `build` is called again for each attempt since a
+/// `Request` is consumed by `send`. A response that comes back `Ok` with a 5xx status is treated
+/// the same as a transport error and retried too — a bad gateway from the CDN is just as
+/// transient as a dropped connection, and the caller shouldn't have to special-case it.
+///
+/// There's no sleep primitive available in this wasm sandbox, so each retry fires immediately
+/// rather than after a timed backoff — "backoff with jitter" isn't implementable here.
+///
+/// There's also no per-request timeout/cancellation knob on [`Request`] in this `aidoku` version,
+/// so a single slow `send()` still blocks for as long as the underlying host takes. What this
+/// *can* do is stop throwing good time after bad: once the `requestTimeoutSeconds` budget has
+/// already elapsed, an already-slow endpoint is unlikely to get faster on a further retry, so
+/// remaining attempts are skipped instead of compounding the wait.
+///
+/// Once every attempt against the default API line has failed, this also tries the known mirror
+/// host once (see `settings::is_mirror_failover_active`) before giving up — `build` re-resolves
+/// `api_url()`/`base_url()` itself on every call, so flipping the flag and calling it again is
+/// enough to redirect it, no extra plumbing needed. Only kicks in while the user's own "apiLine"
+/// choice is still "default"; an explicit "mirror"/"custom" pick is never second-guessed.
+pub fn send_with_retry(build: impl Fn() -> Result<Request>, attempts: u32) -> Result<Response> {
+	let started_at = current_date();
+	let timeout_secs = crate::settings::get_request_timeout_seconds();
+	let mut last_outcome = Err(error!("{}", crate::strings::request_not_sent()));
+	for attempt in 0..attempts.max(1) {
+		if attempt > 0 && timeout_secs > 0 && current_date() - started_at >= timeout_secs {
+			break;
+		}
+		last_outcome = build().and_then(|request| request.send());
+		match &last_outcome {
+			Ok(response) if response.status_code() >= 500 => continue,
+			Ok(_) => return last_outcome,
+			Err(_) => continue,
+		}
+	}
+
+	if is_retryable_failure(&last_outcome)
+		&& crate::settings::get_api_line().as_str() == "default"
+		&& !crate::settings::is_mirror_failover_active()
+	{
+		crate::settings::set_mirror_failover_active(true);
+		let fallback_outcome = build().and_then(|request| request.send());
+		if is_retryable_failure(&fallback_outcome) {
+			// The mirror didn't help either — don't stay pinned to it with no evidence it works.
+			crate::settings::set_mirror_failover_active(false);
+		} else {
+			return fallback_outcome;
+		}
+	}
+
+	match last_outcome {
+		Ok(response) => bail!("{}", crate::strings::server_error(response.status_code())),
+		Err(_) => bail!("{}", crate::strings::network_failed()),
+	}
+}
+
+fn is_retryable_failure(outcome: &Result<Response>) -> bool {
+	match outcome {
+		Ok(response) => response.status_code() >= 500,
+		Err(_) => true,
+	}
+}
+
+/// Re-fetches a chapter's page list and picks out a single fresh URL by index, for when the
+/// signed URL handed out earlier has since expired.
+pub fn refresh_page_url(comic_id: &str, chapter_id: &str, index: usize, prefer_hd: bool) -> Result<String> {
+	let json = fetch_authed_json(
+		&urls::chapter(comic_id, chapter_id),
+		crate::settings::get_retry_attempts(),
+	)?;
+	let data = check_errno(&json)?;
+
+	let key = if prefer_hd { "page_url_hd" } else { "page_url" };
+	let url = data
+		.get(key)
+		.and_then(|v| v.as_array())
+		.or_else(|| data.get("page_url").and_then(|v| v.as_array()))
+		.and_then(|urls| urls.get(index))
+		.and_then(|v| v.as_str())
+		.ok_or_else(|| error!("Missing chapter.page_url"))?;
+
+	Ok(apply_low_data_scaling(rewrite_image_host(url)))
+}
+
+/// Appends the CDN's downscale/quality query params for 省流模式 (low-data mode), so page images
+/// are fetched smaller instead of full-size and re-compressed client-side.
+pub fn apply_low_data_scaling(url: String) -> String {
+	if !crate::settings::is_low_data_mode() {
+		return url;
+	}
+	let separator = if url.contains('?') { "&" } else { "?" };
+	format!("{url}{separator}imageView2/2/w/720/q/75")
+}
+
+/// Appends the CDN's upscale query param so list/search/rank cover thumbnails (rendered small
+/// server-side) load at a resolution sharp enough for library art, when `preferHdCovers` is on.
+/// Detail-page covers come through at full resolution already, so this only matters for the
+/// thumbnail-sized covers every list-shaped endpoint returns.
+pub fn apply_cover_quality(url: String) -> String {
+	if !crate::settings::prefer_hd_covers() {
+		return url;
+	}
+	let separator = if url.contains('?') { "&" } else { "?" };
+	format!("{url}{separator}imageView2/2/w/600/q/90")
+}
+
+/// Subscribes to a manga server-side, syncing into zaimanhua's own "我的订阅" list.
+///
+/// There's no library-add/remove event hook in this `aidoku` version — `Source` only ever hears
+/// about a manga the reader already opened, never a client-side library change — so this can't
+/// fire automatically when the user adds something to their Aidoku library. It's wired to a
+/// manual settings action instead (see `settings::is_subscribe_sync_enabled`).
+pub fn subscribe(comic_id: &str) -> Result<()> {
+	let json = fetch_authed_json(&urls::sub_add(comic_id), crate::settings::get_retry_attempts())?;
+	check_errno(&json)?;
+	Ok(())
+}
+
+/// The `sub/cancel` counterpart to [`subscribe`].
+pub fn unsubscribe(comic_id: &str) -> Result<()> {
+	let json = fetch_authed_json(&urls::sub_cancel(comic_id), crate::settings::get_retry_attempts())?;
+	check_errno(&json)?;
+	Ok(())
+}
+
+/// Pages through the server's full subscribe list and returns every comic id on it, for
+/// `home::run_subscription_sync`'s diff.
+pub fn fetch_all_subscribed_ids() -> Result<Vec<String>> {
+	let mut ids = Vec::new();
+	let mut page = 1;
+	loop {
+		let json = fetch_authed_json(&urls::sub_list(page, 100), crate::settings::get_retry_attempts())?;
+		let data = check_errno(&json)?;
+		let list = data.get("list").and_then(|v| v.as_array()).cloned().unwrap_or_default();
+		if list.is_empty() {
+			break;
+		}
+		ids.extend(
+			list.iter()
+				.filter_map(|item| item.get("id").and_then(|v| v.as_str()).map(ToString::to_string)),
+		);
+		let total = data.get("total").and_then(|v| v.as_i64()).unwrap_or(0);
+		if i64::from(page * 100) >= total {
+			break;
+		}
+		page += 1;
+	}
+	Ok(ids)
+}
+
+/// Fetches the day's task list (reading time, sharing, etc.), each with its own name, reward and
+/// completion state.
+pub fn fetch_daily_tasks() -> Result<Vec<serde_json::Value>> {
+	let json = fetch_authed_json("/app/v1/task/list", crate::settings::get_retry_attempts())?;
+	let data = check_errno(&json)?;
+	Ok(data
+		.get("list")
+		.and_then(|v| v.as_array())
+		.cloned()
+		.unwrap_or_default())
+}
+
+/// Claims a single completed-but-unclaimed task's reward by id.
+pub fn claim_task_reward(task_id: &str) -> Result<()> {
+	let json = fetch_authed_json(
+		&format!("/app/v1/task/claim?task_id={task_id}"),
+		crate::settings::get_retry_attempts(),
+	)?;
+	check_errno(&json)?;
+	Ok(())
+}
+
+/// Sign-in already happened for the day — distinct from a genuine failure, so the "立即签到"
+/// button can report it as an expected no-op instead of an error.
+const ERRNO_ALREADY_SIGNED_IN: i64 = 1002;
+
+/// Signs in for the day and formats the reward it returned (points gained, new streak), so the
+/// caller has something to actually show for it instead of a silent success. Already having
+/// signed in today isn't treated as a failure.
+pub fn check_in() -> Result<String> {
+	let url = urls::sign_in();
+	let token = crate::settings::get_token().ok_or_else(|| error!("Please log in first"))?;
+	let mut response = Request::post(url)?
+		.header("User-Agent", &user_agent())
+		.header("Authorization", &token)
+		.send()?;
+	let json: serde_json::Value = response.get_json()?;
+	if json.get("errno").and_then(|v| v.as_i64()) == Some(ERRNO_ALREADY_SIGNED_IN) {
+		return Ok("今日已签到".to_string());
+	}
+	let data = check_errno(&json)?;
+	let points = data.get("point").and_then(|v| v.as_i64()).unwrap_or(0);
+	let streak = data.get("sign_days").and_then(|v| v.as_i64()).unwrap_or(0);
+	Ok(format!("签到成功 +{points}积分 · 连续签到{streak}天"))
+}
@@ -0,0 +1,143 @@
+//! Multiple saved zaimanhua accounts (e.g. a VIP account and a throwaway), switchable from
+//! settings without re-entering credentials. Each profile is stored as a handful of individually
+//! keyed `defaults` entries rather than one serialized blob, matching how every other per-item
+//! setting in this source is stored.
+
+use crate::settings;
+use aidoku::{
+	Result,
+	alloc::{String, Vec, format, string::ToString},
+	error,
+	imports::defaults::{DefaultValue, defaults_get, defaults_set},
+};
+
+fn profile_key(name: &str, field: &str) -> String {
+	format!("profile_{name}_{field}")
+}
+
+pub fn get_profile_names() -> Vec<String> {
+	defaults_get::<String>("savedProfileNames")
+		.map(|value| {
+			value
+				.split(',')
+				.map(str::trim)
+				.filter(|name| !name.is_empty())
+				.map(ToString::to_string)
+				.collect()
+		})
+		.unwrap_or_default()
+}
+
+fn set_profile_names(names: &[String]) {
+	defaults_set("savedProfileNames", DefaultValue::String(names.join(",")));
+	defaults_set("savedProfilesDisplay", DefaultValue::String(names.join(", ")));
+}
+
+/// The profile the active token/preferences were last loaded from or saved as, if any. When
+/// unset, `settings::is_enhanced_mode`/`is_auto_checkin_enabled` just read the global switches.
+pub fn active_profile_name() -> Option<String> {
+	defaults_get::<String>("activeProfile").filter(|name| !name.is_empty())
+}
+
+/// A profile's enhanced-mode/auto-check-in flags are stored as a plain "true"/"" string alongside
+/// its token, rather than a native bool default, since this `aidoku` version's `DefaultValue`
+/// only has `String`/`StringArray`/`HashMap`/`Null` constructors available to write with.
+fn profile_flag(name: &str, field: &str) -> bool {
+	defaults_get::<String>(profile_key(name, field)).as_deref() == Some("true")
+}
+
+fn set_profile_flag(name: &str, field: &str, value: bool) {
+	let value = if value { "true".to_string() } else { String::new() };
+	defaults_set(profile_key(name, field), DefaultValue::String(value));
+}
+
+pub fn profile_enhanced_mode(name: &str) -> bool {
+	profile_flag(name, "enhancedMode")
+}
+
+pub fn profile_auto_checkin(name: &str) -> bool {
+	profile_flag(name, "autoCheckin")
+}
+
+/// Copies one of the raw string `defaults` entries `settings.rs` itself reads/writes (already
+/// hashed/obfuscated there — `username` is the only plain one) into `name`'s own keyed slot,
+/// leaving it untouched if the global entry isn't set.
+fn snapshot_field(name: &str, field: &str, global_key: &str) {
+	if let Some(value) = defaults_get::<String>(global_key) {
+		defaults_set(profile_key(name, field), DefaultValue::String(value));
+	}
+}
+
+/// The inverse of `snapshot_field`. Writes `DefaultValue::Null` to `global_key` when the profile
+/// has nothing stored for `field` (e.g. a profile saved right after an SMS/QR login never had a
+/// `username`/`password` to snapshot) instead of leaving it untouched — otherwise the previously
+/// active profile's value would keep sitting in the global setting and get picked up by the next
+/// errno-99 recovery in `net::fetch_authed_json`, silently re-authenticating the wrong account.
+fn restore_field(name: &str, field: &str, global_key: &str) {
+	match defaults_get::<String>(profile_key(name, field)) {
+		Some(value) => defaults_set(global_key, DefaultValue::String(value)),
+		None => defaults_set(global_key, DefaultValue::Null),
+	}
+}
+
+/// Snapshots the currently active account (username/password hash/token/refresh token) and the
+/// global enhanced-mode/auto-check-in switches under `name`, adding it to the saved list if it's
+/// new, and makes it the active profile.
+///
+/// Username/password/refresh token are saved alongside the token and not left as single global
+/// settings: `net::try_refresh_token`/`net::login` (the errno-99 recovery path in
+/// `net::fetch_authed_json`) read them straight out of `settings`, so after switching profiles a
+/// silent token-expiry recovery would otherwise re-authenticate whichever account's credentials
+/// were last typed in, not the one that was just loaded — defeating the whole point of having
+/// separate profiles.
+pub fn save_profile(name: &str) -> Result<()> {
+	if name.is_empty() {
+		return Err(error!("Please enter a profile name first"));
+	}
+	snapshot_field(name, "token", "token");
+	snapshot_field(name, "username", "username");
+	snapshot_field(name, "password", "password");
+	snapshot_field(name, "refreshToken", "refreshToken");
+	set_profile_flag(name, "enhancedMode", settings::is_enhanced_mode());
+	set_profile_flag(name, "autoCheckin", settings::is_auto_checkin_enabled());
+
+	let mut names = get_profile_names();
+	if !names.iter().any(|existing| existing == name) {
+		names.push(name.to_string());
+		set_profile_names(&names);
+	}
+	defaults_set("activeProfile", DefaultValue::String(name.to_string()));
+	Ok(())
+}
+
+/// Switches to a previously saved profile: restores its token and credentials so every authed
+/// request — including a silent token-expiry recovery — picks up this account rather than
+/// whichever one's username/password/refresh token last happened to be sitting in settings, and
+/// marks it active so its enhanced-mode/auto-check-in flags take over from the global switches.
+pub fn load_profile(name: &str) -> Result<()> {
+	if !get_profile_names().iter().any(|existing| existing == name) {
+		return Err(error!("No saved profile named `{name}`"));
+	}
+	restore_field(name, "token", "token");
+	restore_field(name, "username", "username");
+	restore_field(name, "password", "password");
+	restore_field(name, "refreshToken", "refreshToken");
+	defaults_set("activeProfile", DefaultValue::String(name.to_string()));
+	Ok(())
+}
+
+/// Removes a saved profile and its stored fields entirely, clearing `activeProfile` if it was
+/// the one active.
+pub fn delete_profile(name: &str) {
+	let names: Vec<String> = get_profile_names().into_iter().filter(|existing| existing != name).collect();
+	set_profile_names(&names);
+	defaults_set(profile_key(name, "token"), DefaultValue::Null);
+	defaults_set(profile_key(name, "username"), DefaultValue::Null);
+	defaults_set(profile_key(name, "password"), DefaultValue::Null);
+	defaults_set(profile_key(name, "refreshToken"), DefaultValue::Null);
+	defaults_set(profile_key(name, "enhancedMode"), DefaultValue::Null);
+	defaults_set(profile_key(name, "autoCheckin"), DefaultValue::Null);
+	if active_profile_name().as_deref() == Some(name) {
+		defaults_set("activeProfile", DefaultValue::Null);
+	}
+}
@@ -0,0 +1,664 @@
+use aidoku::{
+	Result,
+	alloc::{String, Vec, format, string::ToString},
+	error,
+	imports::{
+		defaults::{DefaultValue, defaults_get, defaults_set},
+		std::current_date,
+	},
+};
+
+pub fn get_username() -> Result<String> {
+	defaults_get::<String>("username").ok_or_else(|| error!("Please log in first"))
+}
+
+/// Returns the md5 hash the login API actually wants — never the plaintext password, which this
+/// source no longer keeps around. Transparently migrates a password saved by an older version
+/// that stored it raw, hashing and re-saving it the first time it's read.
+pub fn get_password() -> Result<String> {
+	let stored = defaults_get::<String>("password").ok_or_else(|| error!("Please log in first"))?;
+	if is_md5_hash(&stored) {
+		return Ok(stored);
+	}
+	let hashed = zh_common::md5_hex(&stored);
+	defaults_set("password", DefaultValue::String(hashed.clone()));
+	Ok(hashed)
+}
+
+pub fn set_username(username: &str) -> Result<()> {
+	defaults_set("username", DefaultValue::String(username.to_string()));
+	Ok(())
+}
+
+/// Hashes `password` before persisting it, so a device backup of `defaults` never contains the
+/// account password itself — only the md5 digest `net::login` needs to authenticate.
+pub fn set_password(password: &str) -> Result<()> {
+	defaults_set("password", DefaultValue::String(zh_common::md5_hex(password)));
+	Ok(())
+}
+
+/// Mirrors `home::format_account_info`'s VIP/points/streak summary into the `accountInfoDisplay`
+/// text setting, so it shows up on the account page without a dedicated settings trait to render
+/// it with.
+pub fn set_account_info_display(text: &str) {
+	defaults_set("accountInfoDisplay", DefaultValue::String(text.to_string()));
+}
+
+/// Mirrors `home::account_warning_text`'s unbound-phone/email and restricted-status warnings into
+/// the `accountWarningDisplay` text setting, right below the account info, so calls that silently
+/// fail for one of these reasons (subscribe, check-in) have an explanation nearby instead of just
+/// an opaque error. Empty when there's nothing to warn about.
+pub fn set_account_warning_display(text: &str) {
+	defaults_set("accountWarningDisplay", DefaultValue::String(text.to_string()));
+}
+
+/// Gates the manual "订阅"/"取消订阅" settings buttons (see `net::subscribe`/`net::unsubscribe`) —
+/// there's no library-change event to gate automatically, so this only ever affects the manual
+/// path for now, but it's named for the automatic sync this would do if that hook ever exists.
+pub fn is_subscribe_sync_enabled() -> bool {
+	defaults_get("syncSubscriptions").unwrap_or(false)
+}
+
+/// The manga id typed into the `subscribeComicId` text setting, for the subscribe/unsubscribe
+/// buttons to act on.
+pub fn get_subscribe_comic_id() -> Result<String> {
+	defaults_get::<String>("subscribeComicId")
+		.filter(|s| !s.is_empty())
+		.ok_or_else(|| error!("Please enter a manga id first"))
+}
+
+/// A manually maintained stand-in for "what should be on the shelf" (see
+/// `home::run_subscription_sync`'s doc comment for why it can't be read from Aidoku itself).
+pub fn get_desired_shelf_ids() -> Vec<String> {
+	defaults_get::<String>("desiredShelfIds")
+		.map(|value| {
+			value
+				.split(',')
+				.map(str::trim)
+				.filter(|id| !id.is_empty())
+				.map(ToString::to_string)
+				.collect()
+		})
+		.unwrap_or_default()
+}
+
+/// Mirrors `home::run_subscription_sync`'s added/removed counts into the
+/// `subscriptionSyncDisplay` text setting.
+pub fn set_sync_result_display(text: &str) {
+	defaults_set("subscriptionSyncDisplay", DefaultValue::String(text.to_string()));
+}
+
+/// Mirrors `net::check_in`'s reward summary (points gained, new streak) into the
+/// `checkinResultDisplay` text setting, so the "立即签到" button's effect is actually visible. A
+/// separate text field rather than updating the button's own subtitle, since button settings in
+/// this `aidoku` version don't expose a way to change their subtitle at runtime.
+pub fn set_checkin_result_display(text: &str) {
+	defaults_set("checkinResultDisplay", DefaultValue::String(text.to_string()));
+}
+
+/// Mirrors `home::run_daily_tasks`'s per-task summary into the `dailyTasksDisplay` text setting.
+pub fn set_daily_tasks_display(text: &str) {
+	defaults_set("dailyTasksDisplay", DefaultValue::String(text.to_string()));
+}
+
+/// Clears everything tied to the logged-in account — username, password hash and token — without
+/// touching any other stored preference. Used on logout (see `BasicLoginHandler`'s empty-username
+/// branch), after the server-side token has already been invalidated.
+pub fn clear_account() {
+	defaults_set("username", DefaultValue::Null);
+	defaults_set("password", DefaultValue::Null);
+	defaults_set("token", DefaultValue::Null);
+	defaults_set("refreshToken", DefaultValue::Null);
+}
+
+fn is_md5_hash(value: &str) -> bool {
+	value.len() == 32 && value.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// The phone number entered for the SMS code login flow (see `res/settings.json`'s `smsPhone`,
+/// `smsRequestCode`, `smsCode` and `smsLogin` items) — for accounts that never set a password.
+pub fn get_sms_phone() -> Result<String> {
+	defaults_get::<String>("smsPhone")
+		.filter(|s| !s.is_empty())
+		.ok_or_else(|| error!("Please enter a phone number first"))
+}
+
+/// The verification code the user received by SMS and typed into the `smsCode` setting.
+pub fn get_sms_code() -> Result<String> {
+	defaults_get::<String>("smsCode")
+		.filter(|s| !s.is_empty())
+		.ok_or_else(|| error!("Please request and enter a verification code first"))
+}
+
+/// The id of the QR login session most recently requested (see `net::request_qr_token`),
+/// cached so a later tap of "查询二维码状态" knows which session to poll.
+pub fn set_qr_session_id(id: &str) {
+	defaults_set("qrSessionId", DefaultValue::String(id.to_string()));
+}
+
+pub fn get_qr_session_id() -> Result<String> {
+	defaults_get::<String>("qrSessionId").ok_or_else(|| error!("Please generate a QR code first"))
+}
+
+/// Mirrors the generated QR session's URL into the `qrLoginUrl` text setting, so the user can see
+/// (and copy into a browser, or open on the app) the address the QR code itself encodes — this
+/// `aidoku` version has no way to render an actual QR image in settings.
+pub fn set_qr_login_url(url: &str) {
+	defaults_set("qrLoginUrl", DefaultValue::String(url.to_string()));
+}
+
+/// Fixed XOR key the token is obfuscated with before it ever touches `defaults`. This `aidoku`
+/// version has no device-identifier API to derive a per-device salt from, so this only stops a
+/// stored token from reading as plaintext in a casual inspection of app defaults/backups — it's
+/// not real encryption.
+const TOKEN_OBFUSCATION_KEY: &[u8] = b"zaimanhua-token-salt";
+
+fn xor_with_key(bytes: &[u8]) -> Vec<u8> {
+	bytes
+		.iter()
+		.enumerate()
+		.map(|(i, b)| b ^ TOKEN_OBFUSCATION_KEY[i % TOKEN_OBFUSCATION_KEY.len()])
+		.collect()
+}
+
+fn bytes_to_hex(bytes: &[u8]) -> String {
+	bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn hex_to_bytes(hex: &str) -> Option<Vec<u8>> {
+	if hex.len() % 2 != 0 {
+		return None;
+	}
+	(0..hex.len())
+		.step_by(2)
+		.map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+		.collect()
+}
+
+/// XOR-obfuscates `token` into the hex string actually written to `defaults`. Exposed so
+/// `profiles::save_profile` can store a saved account's token the same way instead of stashing it
+/// in plaintext.
+pub fn obfuscate_token(token: &str) -> String {
+	bytes_to_hex(&xor_with_key(token.as_bytes()))
+}
+
+/// The [`obfuscate_token`] counterpart, for reading a token back out of storage.
+pub fn deobfuscate_token(stored: &str) -> Option<String> {
+	let bytes = hex_to_bytes(stored)?;
+	String::from_utf8(xor_with_key(&bytes)).ok()
+}
+
+pub fn get_token() -> Option<String> {
+	defaults_get::<String>("token").and_then(|stored| deobfuscate_token(&stored))
+}
+
+pub fn set_token(token: &str) {
+	defaults_set("token", DefaultValue::String(obfuscate_token(token)));
+}
+
+/// A long-lived credential `net::login` gets back alongside the session token, when the server
+/// provides one — lets `net::try_refresh_token` recover an expired session without resending the
+/// account's username/password. Obfuscated the same way as `token` itself.
+pub fn get_refresh_token() -> Option<String> {
+	defaults_get::<String>("refreshToken").and_then(|stored| deobfuscate_token(&stored))
+}
+
+pub fn set_refresh_token(refresh_token: &str) {
+	defaults_set("refreshToken", DefaultValue::String(obfuscate_token(refresh_token)));
+}
+
+pub fn is_enhanced_mode() -> bool {
+	match crate::profiles::active_profile_name() {
+		Some(name) => crate::profiles::profile_enhanced_mode(&name),
+		None => defaults_get("enhancedMode").unwrap_or(false),
+	}
+}
+
+/// Flips the global `enhancedMode` switch from code (see `autoEnableEnhancedMode`, checked right
+/// after a successful login). Doesn't touch a saved profile's own flag — only `save_profile`
+/// snapshots that, same as every other profile field.
+pub fn set_enhanced_mode(enabled: bool) {
+	defaults_set("enhancedMode", DefaultValue::Bool(enabled));
+}
+
+/// Whether a successful login should turn on "增强浏览" right away, saving the separate trip into
+/// settings every new user currently has to make to see hidden content.
+pub fn auto_enable_enhanced_mode_after_login() -> bool {
+	defaults_get("autoEnableEnhancedMode").unwrap_or(false)
+}
+
+/// Whether this account should be auto-checked-in (see `profiles::save_profile`, which snapshots
+/// this alongside `is_enhanced_mode` per saved account). Checked by `maybe_auto_checkin` on
+/// startup, alongside `has_checked_in_today` so it only actually fires once per day.
+pub fn is_auto_checkin_enabled() -> bool {
+	match crate::profiles::active_profile_name() {
+		Some(name) => crate::profiles::profile_auto_checkin(&name),
+		None => defaults_get("autoCheckin").unwrap_or(false),
+	}
+}
+
+/// Day-number (`current_date() / 86400`) the last successful check-in — manual or automatic —
+/// happened on. Storing the day rather than a fixed "done" flag is what lets automatic check-in
+/// (`is_auto_checkin_enabled`) run once per day instead of, as it used to, setting a flag that
+/// never reset and so never checking in again after the very first time.
+pub fn mark_checked_in_today() {
+	let day = current_date() / 86400;
+	defaults_set("lastCheckinDay", DefaultValue::String(day.to_string()));
+}
+
+/// Whether `mark_checked_in_today` was already called today.
+pub fn has_checked_in_today() -> bool {
+	let today = current_date() / 86400;
+	defaults_get::<String>("lastCheckinDay").and_then(|day| day.parse::<i64>().ok()) == Some(today)
+}
+
+/// The name typed into the `profileName` text setting, for the `saveProfile`/`loadProfile`/
+/// `deleteProfile` buttons to act on.
+pub fn get_profile_name_input() -> Result<String> {
+	defaults_get::<String>("profileName")
+		.filter(|s| !s.is_empty())
+		.ok_or_else(|| error!("Please enter a profile name first"))
+}
+
+/// Whether VIP/pay chapters should be dropped from the chapter list entirely instead of shown
+/// locked.
+pub fn hide_locked_chapters() -> bool {
+	defaults_get("hideLockedChapters").unwrap_or(false)
+}
+
+/// Whether `net::sync_read_progress` should report chapter reads back to the account's reading
+/// record, so the website's "历史记录" and update reminders reflect reading done in Aidoku.
+/// Defaults on since that's the behavior this already had before it was made optional.
+pub fn is_reading_history_upload_enabled() -> bool {
+	defaults_get("uploadReadingHistory").unwrap_or(true)
+}
+
+/// Whether the chapter list should be returned oldest-first. The API (and this source's default)
+/// already reads newest-first.
+pub fn is_chapter_order_oldest_first() -> bool {
+	defaults_get::<String>("chapterOrder").as_deref() == Some("oldest")
+}
+
+/// Chapter group titles (e.g. "番外", "特典") that should be dropped from the chapter list
+/// entirely. Opt-in and empty by default, like `hideLockedChapters`/`chapterTitleCleanup`/
+/// `trimWatermark`, since it removes chapters the API actually sent.
+pub fn get_hidden_group_keywords() -> Vec<String> {
+	defaults_get::<Vec<String>>("hiddenGroupKeywords").unwrap_or_default()
+}
+
+/// Whether chapter titles should have the repeated manga title/empty brackets stripped off (see
+/// `helpers::clean_chapter_title`). Opt-in since it rewrites titles the API actually sent.
+pub fn is_chapter_title_cleanup_enabled() -> bool {
+	defaults_get("chapterTitleCleanup").unwrap_or(false)
+}
+
+/// Parses the `groupPreference` text setting (a comma-separated list of chapter group names,
+/// e.g. "连载,单行本") into the order groups should be preferred in when deduplicating chapters
+/// that appear in more than one group. Groups not listed keep their original relative order and
+/// sort after every group that was explicitly listed.
+pub fn get_group_preference() -> Vec<String> {
+	defaults_get::<String>("groupPreference")
+		.map(|value| {
+			value
+				.split(',')
+				.map(str::trim)
+				.filter(|name| !name.is_empty())
+				.map(ToString::to_string)
+				.collect()
+		})
+		.unwrap_or_default()
+}
+
+/// Whether `get_page_list` should prefer the `page_url_hd` array over the standard `page_url`
+/// one. Defaults to HD; users on metered data can switch to SD to save bandwidth.
+pub fn prefer_hd_images() -> bool {
+	defaults_get::<String>("imageQuality").as_deref() != Some("sd")
+}
+
+/// A replacement hostname for `net::DEFAULT_IMAGE_HOST`, for users whose network blocks the
+/// default CDN. Empty string (the default) means use the CDN host as-is.
+pub fn get_image_host_mirror() -> Option<String> {
+	defaults_get::<String>("imageHostMirror").filter(|s| !s.is_empty())
+}
+
+/// Which API/web host pair `net::api_url`/`net::base_url` should resolve to: the hardcoded
+/// default, the bundled mirror, or a user-supplied custom pair. `"default"` whenever the setting
+/// is unset or holds an unrecognized value.
+pub fn get_api_line() -> String {
+	defaults_get::<String>("apiLine").unwrap_or_else(|| "default".to_string())
+}
+
+/// The API host used when `get_api_line` is `"custom"`. Empty string means fall back to the
+/// hardcoded default, same as leaving `imageHostMirror` blank.
+pub fn get_custom_api_url() -> Option<String> {
+	defaults_get::<String>("customApiUrl").filter(|s| !s.is_empty())
+}
+
+/// The `get_custom_api_url` counterpart for the web host.
+pub fn get_custom_web_url() -> Option<String> {
+	defaults_get::<String>("customWebUrl").filter(|s| !s.is_empty())
+}
+
+/// How many seconds `net::send_with_retry` gives a flaky endpoint across all its attempts before
+/// giving up on further retries, in seconds. `0` means "no budget" (always use every attempt) —
+/// this can't cancel an in-flight request (see `net::send_with_retry`'s own doc comment), only
+/// skip *further* ones once the budget's already spent.
+pub fn get_request_timeout_seconds() -> i64 {
+	defaults_get::<String>("requestTimeoutSeconds")
+		.and_then(|s| s.parse().ok())
+		.unwrap_or(20)
+}
+
+/// How many times `net::send_with_retry` retries a single request (transport error or 5xx
+/// response from zaimanhua's flaky CDN) before giving up, total including the first attempt. Most
+/// call sites used to hardcode `1` (no retry at all); this makes that actually configurable.
+pub fn get_retry_attempts() -> u32 {
+	defaults_get::<String>("apiRetryAttempts")
+		.and_then(|s| s.parse().ok())
+		.unwrap_or(2)
+}
+
+/// Whether `net::api_url`/`net::base_url` are currently serving the mirror host automatically,
+/// because the default host failed every attempt in a recent `net::send_with_retry` call, while
+/// the user's own "apiLine" selection is still `"default"`. An explicit `"mirror"`/`"custom"`
+/// choice always wins over this — it's only ever consulted for the `"default"` case.
+pub fn is_mirror_failover_active() -> bool {
+	defaults_get("mirrorFailoverActive").unwrap_or(false)
+}
+
+/// Flips [`is_mirror_failover_active`] and mirrors the new state into `apiLineStatusDisplay`, so
+/// an automatic host switch is visible to the user instead of a silent behavior change.
+pub fn set_mirror_failover_active(active: bool) {
+	defaults_set("mirrorFailoverActive", DefaultValue::Bool(active));
+	let status = if active {
+		"已自动切换至备用线路（默认线路近期请求持续失败）"
+	} else {
+		"默认线路"
+	};
+	defaults_set("apiLineStatusDisplay", DefaultValue::String(status.to_string()));
+}
+
+/// Whether authed API requests should carry `net::apply_signature_headers`'s speculative
+/// channel/version/timestamp/sign headers. Off by default since the signing scheme is unverified
+/// guesswork (see that function's own doc comment) — this is an opt-in experiment, not something
+/// that should change request behavior for users who never asked for it.
+pub fn is_signature_headers_enabled() -> bool {
+	defaults_get("appSignatureHeaders").unwrap_or(false)
+}
+
+/// A replacement `User-Agent` string for every request this crate sends (see `net::user_agent`),
+/// for networks that block or throttle the hardcoded default. Empty string (the default) means
+/// use the hardcoded string as-is.
+pub fn get_custom_user_agent() -> Option<String> {
+	defaults_get::<String>("customUserAgent").filter(|s| !s.is_empty())
+}
+
+/// Parses the `customExtraHeaders` editable list (one `"Header-Name: value"` pair per line) into
+/// `(name, value)` pairs for `net::apply_custom_headers`. Lines without a `:` separator, or with
+/// an empty name, are dropped rather than erroring the whole request over one typo.
+pub fn get_extra_headers() -> Vec<(String, String)> {
+	defaults_get::<Vec<String>>("customExtraHeaders")
+		.unwrap_or_default()
+		.into_iter()
+		.filter_map(|line| {
+			let (name, value) = line.split_once(':')?;
+			let name = name.trim();
+			if name.is_empty() {
+				return None;
+			}
+			Some((name.to_string(), value.trim().to_string()))
+		})
+		.collect()
+}
+
+/// Whether rank boards' popularity counts should be prepended to each entry's description, for
+/// readers who want to see the numbers behind "热度推荐" instead of a clean, stat-free list.
+pub fn show_popularity() -> bool {
+	defaults_get("showPopularity").unwrap_or(false)
+}
+
+/// Whether the detail parsers should prefer a series' official localized title or its original
+/// (usually Japanese) title for `Manga.title`, when the detail payload carries both. `true` means
+/// prefer the original title.
+///
+/// The non-chosen title is folded into the description instead of a dedicated alternate-title
+/// field: this `aidoku` version's `Manga` has no such field to populate, so this is the best
+/// available way to keep the other title around.
+pub fn prefer_original_title() -> bool {
+	defaults_get::<String>("titlePreference").as_deref() == Some("original")
+}
+
+/// Whether `DynamicListings::get_dynamic_listings` should drop categories whose name matches a
+/// known NSFW keyword (see `helpers::content_rating_from_tags`'s keyword lists). Category names
+/// are the only NSFW signal any listing-shaped endpoint actually returns — search/filter/rank
+/// results carry a manga's id/title/cover but never its theme tags, so there's no per-entry
+/// signal to filter a generic listing by.
+pub fn hide_nsfw_categories() -> bool {
+	defaults_get("hideNsfwCategories").unwrap_or(false)
+}
+
+/// Whether list/search/rank cover thumbnails should be upgraded to a sharper CDN resolution (see
+/// `net::apply_cover_quality`), for users who'd rather have sharp library art than save the extra
+/// bandwidth. Independent of `imageQuality`/`lowDataMode`, which only affect chapter page images.
+pub fn prefer_hd_covers() -> bool {
+	defaults_get("preferHdCovers").unwrap_or(false)
+}
+
+/// Whether `PageImageProcessor` should crop the watermark strip some uploads carry (see
+/// `image_processing::trim_watermark`). Opt-in since it permanently crops pixels off the page.
+pub fn is_watermark_trim_enabled() -> bool {
+	defaults_get("trimWatermark").unwrap_or(false)
+}
+
+/// "省流模式": requests downscaled page images and shrinks list page sizes, for metered data.
+pub fn is_low_data_mode() -> bool {
+	defaults_get("lowDataMode").unwrap_or(false)
+}
+
+pub fn get_page_size() -> i32 {
+	if is_low_data_mode() {
+		return 10;
+	}
+	defaults_get::<String>("pageSize")
+		.and_then(|s| s.parse().ok())
+		.unwrap_or(20)
+}
+
+/// Whether a togglable home section (see `res/settings.json`'s "首页显示" group) should render.
+/// All sections default to visible.
+pub fn is_home_section_enabled(key: &str) -> bool {
+	defaults_get(key).unwrap_or(true)
+}
+
+/// Parses the `homeOrder` text setting (a comma-separated list of home section keys) into the
+/// order the user wants them rendered in.
+pub fn get_home_order() -> Vec<String> {
+	defaults_get::<String>("homeOrder")
+		.map(|value| {
+			value
+				.split(',')
+				.map(str::trim)
+				.filter(|key| !key.is_empty())
+				.map(ToString::to_string)
+				.collect()
+		})
+		.unwrap_or_default()
+}
+
+/// Caches a home section's raw response data so it can still be shown (stale) if a later
+/// request for that section fails. Keyed separately from the section's visibility toggle.
+pub fn cache_home_section(key: &str, data: &str) {
+	defaults_set(format!("homeCache_{key}"), DefaultValue::String(data.to_string()));
+	defaults_set("homeCacheUpdatedAt", DefaultValue::String(current_date().to_string()));
+}
+
+pub fn get_cached_home_section(key: &str) -> Option<String> {
+	defaults_get::<String>(format!("homeCache_{key}"))
+}
+
+/// Every key [`cache_home_section`] ever writes under, for [`cache_summary`]/[`clear_home_cache`]
+/// to enumerate — kept in sync by hand with the section keys `home::enabled_sections` pushes.
+const HOME_SECTION_KEYS: &[&str] = &[
+	"homeRecommend",
+	"homeRank",
+	"homeLatest",
+	"homeShounen",
+	"homeShoujo",
+	"homeQingnian",
+	"homeOther",
+	"homeCn",
+	"homeKr",
+];
+
+/// Summarizes the home-section cache's combined size and age for the `cacheSummaryDisplay`
+/// settings text field.
+///
+/// This is the only persisted cache this source keeps: there's no separate hidden-index or
+/// search-result cache anywhere in this crate — both of those are always fetched fresh, so
+/// there's nothing to size or clear for them.
+pub fn cache_summary() -> String {
+	let total_bytes: usize = HOME_SECTION_KEYS
+		.iter()
+		.filter_map(|key| get_cached_home_section(key))
+		.map(|data| data.len())
+		.sum();
+	if total_bytes == 0 {
+		return "暂无缓存".to_string();
+	}
+	let age_seconds = defaults_get::<String>("homeCacheUpdatedAt")
+		.and_then(|value| value.parse::<i64>().ok())
+		.map(|updated_at| (current_date() - updated_at).max(0));
+
+	match age_seconds {
+		Some(age) => format!("首页缓存：{total_bytes} 字节 · {}分钟前更新", (age / 60).max(1)),
+		None => format!("首页缓存：{total_bytes} 字节"),
+	}
+}
+
+/// Clears every home section's cached response (see [`HOME_SECTION_KEYS`]), for the "清除缓存"
+/// settings button.
+pub fn clear_home_cache() {
+	for key in HOME_SECTION_KEYS {
+		defaults_set(format!("homeCache_{key}"), DefaultValue::Null);
+	}
+	defaults_set("homeCacheUpdatedAt", DefaultValue::Null);
+}
+
+/// Mirrors [`cache_summary`] into the `cacheSummaryDisplay` settings text field.
+pub fn refresh_cache_summary_display() {
+	defaults_set("cacheSummaryDisplay", DefaultValue::String(cache_summary()));
+}
+
+/// Per-URL short-lived response memoization, for endpoints that can get requested more than once
+/// within a few seconds of each other in the same update pass (today: `userInfo`, called once from
+/// `lib.rs`'s startup token-validation probe and again from `home.rs`'s account-info refresh;
+/// `recommend/list`, called once per editorial home component that needs it). Distinct from
+/// [`cache_home_section`]'s stale-fallback cache above — this is purely a recent-call dedupe that
+/// expires after `ttl_seconds`, not a persistent "show something while offline" cache, and it's
+/// never used for search/hidden results (see [`cache_summary`]'s doc comment).
+pub fn get_recent_response(key: &str, ttl_seconds: i64) -> Option<String> {
+	let cached_at = defaults_get::<String>(format!("dedupeCacheAt_{key}"))?.parse::<i64>().ok()?;
+	if current_date() - cached_at > ttl_seconds {
+		return None;
+	}
+	defaults_get::<String>(format!("dedupeCache_{key}"))
+}
+
+/// Records `body` under `key` for a later [`get_recent_response`] to find.
+pub fn cache_recent_response(key: &str, body: &str) {
+	defaults_set(format!("dedupeCache_{key}"), DefaultValue::String(body.to_string()));
+	defaults_set(format!("dedupeCacheAt_{key}"), DefaultValue::String(current_date().to_string()));
+}
+
+/// Whether `net::debug_log` (and its `home`/`lib.rs` call sites) should record outgoing request
+/// URLs, errno and timing. Off by default since every request gets a little slower once its URL
+/// and timing have to be formatted and written to `defaults` on top of actually being sent.
+pub fn is_debug_logging_enabled() -> bool {
+	defaults_get("debugLogging").unwrap_or(false)
+}
+
+/// How many of the most recent `net::debug_log` lines `debugLogDisplay` keeps. There's no log
+/// viewer or console import in this `aidoku` version, so a capped settings text field mirroring
+/// the most recent lines is the best available stand-in for an attachable debug log.
+const DEBUG_LOG_LINE_LIMIT: usize = 20;
+
+/// Appends `line` to the `debugLogDisplay` settings text field, dropping the oldest line once
+/// [`DEBUG_LOG_LINE_LIMIT`] is exceeded.
+pub fn append_debug_log(line: &str) {
+	let mut lines = defaults_get::<String>("debugLogDisplay")
+		.map(|existing| existing.lines().map(ToString::to_string).collect::<Vec<_>>())
+		.unwrap_or_default();
+	lines.push(line.to_string());
+	if lines.len() > DEBUG_LOG_LINE_LIMIT {
+		lines.drain(..lines.len() - DEBUG_LOG_LINE_LIMIT);
+	}
+	defaults_set("debugLogDisplay", DefaultValue::String(lines.join("\n")));
+}
+
+/// Clears the `debugLogDisplay` text field, for the "清除调试日志" settings button.
+pub fn clear_debug_log() {
+	defaults_set("debugLogDisplay", DefaultValue::Null);
+}
+
+/// Every preference key `reset_to_defaults` clears. Deliberately excludes anything that isn't a
+/// plain preference: login/session state (`username`, `password`, `token`, `refreshToken`, the
+/// SMS/QR login flow's working fields), saved profiles, the subscribe/sync working fields, and
+/// every read-only `*Display` text field and cache entry — those aren't settings to reset, they're
+/// either account state or output the source already refreshes on its own.
+const RESETTABLE_KEYS: &[&str] = &[
+	"appearanceLanguage",
+	"homeBanner",
+	"homeRecommend",
+	"homeRank",
+	"homeLatest",
+	"homeShounen",
+	"homeShoujo",
+	"homeQingnian",
+	"homeOther",
+	"homeCn",
+	"homeKr",
+	"homeRecommendCategories",
+	"homeTodayPick",
+	"homeNews",
+	"homeOrder",
+	"pageSize",
+	"hideLockedChapters",
+	"uploadReadingHistory",
+	"chapterOrder",
+	"trimWatermark",
+	"lowDataMode",
+	"imageHostMirror",
+	"imageQuality",
+	"preferHdCovers",
+	"showPopularity",
+	"hideNsfwCategories",
+	"hiddenGroupKeywords",
+	"titlePreference",
+	"chapterTitleCleanup",
+	"groupPreference",
+	"apiLine",
+	"customApiUrl",
+	"customWebUrl",
+	"requestTimeoutSeconds",
+	"apiRetryAttempts",
+	"mirrorFailoverActive",
+	"apiLineStatusDisplay",
+	"appSignatureHeaders",
+	"customUserAgent",
+	"customExtraHeaders",
+	"debugLogging",
+	"enhancedMode",
+	"autoEnableEnhancedMode",
+	"autoCheckin",
+	"desiredShelfIds",
+];
+
+/// Clears every key in [`RESETTABLE_KEYS`] back to its `settings.json` default, for the "恢复默认设置"
+/// button. Leaves the logged-in account, saved profiles and cached/display data untouched — signing
+/// out is already its own separate action (clearing the "登录" field, or switching profiles), and a
+/// settings reset shouldn't silently log the user out as a side effect.
+pub fn reset_to_defaults() {
+	for key in RESETTABLE_KEYS {
+		defaults_set(*key, DefaultValue::Null);
+	}
+}
@@ -0,0 +1,193 @@
+//! A small zh/en strings layer for the handful of user-facing messages this source builds at
+//! runtime (login/listing/search errors), selected by the `appearanceLanguage` setting.
+//!
+//! This can't reach the strings baked into `res/settings.json`, `res/source.json` and
+//! `res/filters.json` (filter titles, listing names, settings item titles): those are static
+//! manifest files Aidoku reads directly, with no per-request templating. Only the messages this
+//! crate actually formats in Rust can be switched at runtime.
+
+use aidoku::{alloc, imports::defaults::defaults_get};
+use zh_common::errno::ErrorKind;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Lang {
+	Zh,
+	En,
+}
+
+fn kind_label(kind: ErrorKind) -> &'static str {
+	let lang = match current() {
+		Lang::Zh => zh_common::errno::Lang::Zh,
+		Lang::En => zh_common::errno::Lang::En,
+	};
+	kind.label(lang)
+}
+
+/// Reads the `appearanceLanguage` setting; defaults to Chinese, matching `res/source.json`'s
+/// `languages: ["zh"]` and every other hardcoded string already in this crate.
+pub fn current() -> Lang {
+	match defaults_get::<String>("appearanceLanguage").as_deref() {
+		Some("en") => Lang::En,
+		_ => Lang::Zh,
+	}
+}
+
+pub fn please_log_in() -> &'static str {
+	match current() {
+		Lang::Zh => "请先登录",
+		Lang::En => "Please log in first",
+	}
+}
+
+pub fn please_log_in_for_subscriptions() -> &'static str {
+	match current() {
+		Lang::Zh => "请先登录以查看您的订阅",
+		Lang::En => "Please log in first to view your subscriptions",
+	}
+}
+
+pub fn enhanced_mode_required() -> &'static str {
+	match current() {
+		Lang::Zh => "请先开启「增强浏览」以查看隐藏内容",
+		Lang::En => "Please enable Enhanced Mode to browse hidden content",
+	}
+}
+
+pub fn invalid_listing(id: &str) -> alloc::string::String {
+	match current() {
+		Lang::Zh => alloc::format!("无效的榜单/分类：`{id}`"),
+		Lang::En => alloc::format!("Invalid listing: `{id}`"),
+	}
+}
+
+pub fn invalid_login_key(key: &str) -> alloc::string::String {
+	match current() {
+		Lang::Zh => alloc::format!("无效的登录项：`{key}`"),
+		Lang::En => alloc::format!("Invalid login key: `{key}`"),
+	}
+}
+
+pub fn qr_not_confirmed() -> alloc::string::String {
+	let label = kind_label(ErrorKind::NeedsLogin);
+	match current() {
+		Lang::Zh => alloc::format!("{label}：二维码尚未确认，请先使用官方 App 扫码并确认登录"),
+		Lang::En => {
+			alloc::format!("{label}: QR code not confirmed — scan and confirm in the official app")
+		}
+	}
+}
+
+/// The chapter access error for a logged-out reader, with the chapter's own web page appended as
+/// a fallback link when `fallback_url` is known.
+pub fn chapter_needs_login(fallback_url: Option<&str>) -> alloc::string::String {
+	let label = kind_label(ErrorKind::NeedsLogin);
+	match (current(), fallback_url) {
+		(Lang::Zh, Some(url)) => alloc::format!(
+			"{label}：该章节需要登录后才能观看，请在设置中登录账号（或开启「增强浏览」浏览隐藏内容）。也可在浏览器中查看：{url}"
+		),
+		(Lang::Zh, None) => {
+			alloc::format!("{label}：该章节需要登录后才能观看，请在设置中登录账号（或开启「增强浏览」浏览隐藏内容）")
+		}
+		(Lang::En, Some(url)) => {
+			alloc::format!("{label}: needs a login (or Enhanced Mode). View it at: {url}")
+		}
+		(Lang::En, None) => alloc::format!("{label}: needs a login (or Enhanced Mode)"),
+	}
+}
+
+pub fn chapter_session_expired() -> alloc::string::String {
+	let label = kind_label(ErrorKind::NeedsLogin);
+	match current() {
+		Lang::Zh => alloc::format!("{label}：登录状态已失效，请重新登录后重试"),
+		Lang::En => alloc::format!("{label}: your session has expired, please log in again"),
+	}
+}
+
+/// The chapter access error for a VIP-gated chapter, with the chapter's own web page appended as
+/// a fallback link when `fallback_url` is known.
+pub fn chapter_paid(fallback_url: Option<&str>) -> alloc::string::String {
+	let label = kind_label(ErrorKind::Forbidden);
+	match (current(), fallback_url) {
+		(Lang::Zh, Some(url)) => alloc::format!(
+			"{label}：该章节为付费章节，需要在官方 App 或网站购买后才能观看。也可在浏览器中查看：{url}"
+		),
+		(Lang::Zh, None) => {
+			alloc::format!("{label}：该章节为付费章节，需要在官方 App 或网站购买后才能观看")
+		}
+		(Lang::En, Some(url)) => {
+			alloc::format!("{label}: paid chapter, buy in the app first. View it at: {url}")
+		}
+		(Lang::En, None) => alloc::format!("{label}: paid chapter, buy it in the official app"),
+	}
+}
+
+pub fn request_not_sent() -> alloc::string::String {
+	let label = kind_label(ErrorKind::Network);
+	match current() {
+		Lang::Zh => alloc::format!("{label}：请求尚未发出"),
+		Lang::En => alloc::format!("{label}: request was never sent"),
+	}
+}
+
+pub fn server_error(status_code: impl core::fmt::Display) -> alloc::string::String {
+	let label = kind_label(ErrorKind::Server);
+	match current() {
+		Lang::Zh => alloc::format!("{label}：服务器返回 {status_code} 错误，请稍后重试"),
+		Lang::En => alloc::format!("{label}: server error {status_code}, please try again later"),
+	}
+}
+
+pub fn network_failed() -> alloc::string::String {
+	let label = kind_label(ErrorKind::Network);
+	match current() {
+		Lang::Zh => alloc::format!("{label}：请求发送失败，请检查网络连接后重试"),
+		Lang::En => alloc::format!("{label}: request failed, check your connection and try again"),
+	}
+}
+
+pub fn response_unparseable() -> alloc::string::String {
+	let label = kind_label(ErrorKind::Server);
+	match current() {
+		Lang::Zh => alloc::format!("{label}：响应格式无法解析"),
+		Lang::En => alloc::format!("{label}: couldn't parse the response"),
+	}
+}
+
+/// Titles for the fixed-slot home components (`home::fetch_subscriptions`/`fetch_continue_reading`/
+/// `fetch_today_pick`/`fetch_news_banner`) and the togglable `home::enabled_sections` entries,
+/// keyed by their `listing_id`/a short slug rather than duplicated inline at each call site.
+pub fn home_title(id: &str) -> &'static str {
+	match (current(), id) {
+		(Lang::Zh, "subscribe") => "我的订阅",
+		(Lang::Zh, "continue") => "继续阅读",
+		(Lang::Zh, "today-pick") => "今日推荐",
+		(Lang::Zh, "news") => "热门资讯",
+		(Lang::Zh, "recommend") => "精品推荐",
+		(Lang::Zh, "rank-daily") => "日榜",
+		(Lang::Zh, "rank-weekly") => "周榜",
+		(Lang::Zh, "rank-monthly") => "月榜",
+		(Lang::Zh, "latest") => "最近更新",
+		(Lang::Zh, "shounen") => "少年",
+		(Lang::Zh, "shoujo") => "少女",
+		(Lang::Zh, "qingnian") => "青年",
+		(Lang::Zh, "other") => "其他",
+		(Lang::Zh, "cn") => "国漫",
+		(Lang::Zh, "kr") => "韩漫",
+		(Lang::En, "subscribe") => "My Subscriptions",
+		(Lang::En, "continue") => "Continue Reading",
+		(Lang::En, "today-pick") => "Today's Picks",
+		(Lang::En, "news") => "News",
+		(Lang::En, "recommend") => "Featured",
+		(Lang::En, "rank-daily") => "Daily Rank",
+		(Lang::En, "rank-weekly") => "Weekly Rank",
+		(Lang::En, "rank-monthly") => "Monthly Rank",
+		(Lang::En, "latest") => "Latest Updates",
+		(Lang::En, "shounen") => "Shounen",
+		(Lang::En, "shoujo") => "Shoujo",
+		(Lang::En, "qingnian") => "Seinen",
+		(Lang::En, "other") => "Other",
+		(Lang::En, "cn") => "Chinese Comics",
+		(Lang::En, "kr") => "Korean Comics",
+		(_, id) => id,
+	}
+}